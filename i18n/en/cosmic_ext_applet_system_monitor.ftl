@@ -1 +1,6 @@
 loading = Loading...
+top-cpu = Top processes by CPU
+top-memory = Top processes by memory
+top-interrupts = Top IRQ lines by interrupts
+oom-kills = Processes killed when out of memory
+time-ago = { $time } ago
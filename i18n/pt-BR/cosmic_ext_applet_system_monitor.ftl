@@ -1 +1,6 @@
 loading = Carregando...
+top-cpu = Processos com maior uso de CPU
+top-memory = Processos com maior uso de memória
+top-interrupts = Linhas de IRQ com mais interrupções
+oom-kills = Processos encerrados por falta de memória
+time-ago = há { $time }
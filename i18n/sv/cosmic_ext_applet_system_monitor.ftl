@@ -1 +1,6 @@
 loading = Laddar...
+top-cpu = Processer med högst CPU-användning
+top-memory = Processer med högst minnesanvändning
+top-interrupts = IRQ-linjer med flest avbrott
+oom-kills = Processer dödade vid minnesbrist
+time-ago = för { $time } sedan
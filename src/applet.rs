@@ -1,61 +1,998 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
+use chrono::{DateTime, Local};
 use cosmic::{
     app::{Core, Task},
+    applet::padded_control,
     cosmic_config,
-    iced::{Alignment, Padding, Pixels, Size, Subscription},
+    iced::{
+        futures::channel::oneshot,
+        platform_specific::shell::commands::popup::{destroy_popup, get_popup},
+        widget::Stack,
+        window, Alignment, Length, Limits, Padding, Pixels, Size, Subscription,
+    },
     iced_core::padding,
-    widget::{container, Column, Container, Row},
+    widget::{container, divider, mouse_area, text, tooltip, Column, Container, Row},
     Application, Apply as _, Element, Renderer, Theme,
 };
-use std::time::Duration;
-use sysinfo::{Cpu, Disk, Disks, MemoryRefreshKind, Networks, System};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{HashMap, HashSet},
+    mem,
+    net::SocketAddr,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
+};
+use sysinfo::{
+    Cpu, Disk, DiskRefreshKind, Disks, MemoryRefreshKind, Networks, Process, ProcessRefreshKind,
+    ProcessesToUpdate, System,
+};
 
 use crate::{
+    color::Color,
     components::{
-        bar::PercentageBar,
-        run::{HistoryChart, SimpleHistoryChart, SuperimposedHistoryChart},
+        bar::{PercentageBar, SegmentedBar},
+        run::{
+            HeatmapChart, HistoryChart, SimpleHistoryChart, StackedHistoryChart,
+            SuperimposedHistoryChart,
+        },
     },
     config::{
-        config_subscription, ComponentConfig, Config, CpuView, IoView, PaddingOption, PercentView,
+        config_subscription, ComponentConfig, Config, CpuTimeColors, CpuView, FsView, IoView,
+        LatencyProbe, LatencyView, LoadAverage, LoadView, MemoryColors, MemoryView, PaddingOption,
+        PercentView, ProcessCount, ProcessView, PsiValue, RateView, SimpleView, SocketCount,
+        StatRate, TcpView, WifiValue,
     },
+    filter, fl,
     history::History,
+    sources::{
+        battery::{self, Battery, PowerSupply},
+        block::BlockDevices,
+        cgroup::{CgroupSample, Cgroups},
+        command::{self, CommandSample},
+        cpufreq::CpuFreq,
+        diskstats::{DiskStat, DiskStats},
+        gpu::{self, GpuBackend, GpuSample},
+        hwmon::Hwmon,
+        interrupts::{Interrupts, Irq},
+        latency::{self, LatencySample},
+        meminfo::{MemInfo, MemInfoSample},
+        pressure::{Pressure, PressureSample},
+        rapl::{EnergyCounter, Rapl},
+        sockets::{SocketSample, Sockets},
+        stat::{CpuTimes, ProcStat, StatSample},
+        tasks::{TaskSample, Tasks},
+        vmstat::{VmStat, VmStatSample},
+        wireless::{self, Wireless, WirelessSample},
+    },
 };
 
 pub const ID: &str = "dev.DBrox.CosmicSystemMonitor";
 
+/// Number of OOM kill events listed in the popup
+const OOM_EVENTS: usize = 10;
+
 pub struct SystemMonitorApplet {
     core: Core,
     config: Config,
     #[allow(dead_code)]
     config_handler: Option<cosmic_config::Config>,
+    popup: Option<window::Id>,
     // chart: SystemMonitor,
     sys: System,
     nets: Networks,
     disks: Disks,
-    // gpus: Gpus,
+    blocks: BlockDevices,
+    diskstats: DiskStats,
+    hwmon: Hwmon,
+    meminfo: MemInfo,
+    vmstat: VmStat,
+    cgroups: Cgroups,
+    sockets: Sockets,
+    tasks: Tasks,
+    wireless: Wireless,
+    stat: ProcStat,
+    interrupts: Interrupts,
+    pressure: Pressure,
+    power_supply: PowerSupply,
+    rapl: Rapl,
+    /// latest sample of each battery
+    batteries: Vec<Battery>,
+    gpu_backends: Vec<Box<dyn GpuBackend>>,
+    /// latest sample of each GPU
+    gpus: Vec<GpuSample>,
+    /// previous interrupt counts and the time they were read, to compute the rates listed in the popup
+    last_interrupts: Option<(Instant, Vec<Irq>)>,
+    /// IRQ lines with the most interrupts per second, and their rate
+    busiest_irqs: Vec<(Irq, u64)>,
+    /// previous I/O counters and the time they were read, to compute the `Disk` stats between refreshes
+    last_diskstats: Option<(Instant, Vec<DiskStat>)>,
+    /// minimum and maximum frequencies of each core, in MHz
+    frequency_limits: Vec<Option<(u64, u64)>>,
     /// percentage global cpu used between refreshes
     global_cpu: History<f32>,
+    /// percentage of each core used between refreshes
+    core_usages: Vec<History<f32>>,
+    /// percentage of time spent by the cpus in each state between refreshes
+    cpu_times: CpuTimeHistory,
+    /// average frequency of the cores, as a percentage between their minimum and maximum frequencies
+    frequency: History<f32>,
     ram: History,
     swap: History,
+    memory: MemoryHistory,
+    paging: PagingHistory,
+    /// time of the latest refreshes in which the OOM killer killed processes, and the number of them, latest first
+    oom_events: Vec<(DateTime<Local>, u64)>,
+    /// processes killed by the OOM killer since the popup was last opened
+    oom_unseen: u64,
+    load: LoadHistory,
+    tcp: SocketHistory,
+    rates: RateHistory,
+    processes: ProcessHistory,
+    /// temperature of the hottest sensor of each `Temp` component, keyed by the component index
+    temps: HashMap<usize, History<f32>>,
+    /// speed of the fastest fan of each `Fan` component, keyed by the component index
+    fans: HashMap<usize, FanHistory>,
+    /// histories of each `Gpu` component, keyed by the component index
+    gpu_histories: HashMap<usize, GpuHistory>,
+    /// histories of each `Net` component, keyed by the component index
+    net_histories: HashMap<usize, NetHistory>,
+    /// histories of each `Disk` component, keyed by the component index
+    disk_histories: HashMap<usize, DiskHistory>,
+    /// histories of each `Psi` component, keyed by the component index
+    psi_histories: HashMap<usize, PsiHistory>,
+    /// histories of each `Cgroup` component, keyed by the component index
+    cgroup_histories: HashMap<usize, CgroupHistory>,
+    /// histories of each `Wifi` component, keyed by the component index
+    wifi_histories: HashMap<usize, WifiHistory>,
+    /// histories of each `Latency` component, keyed by the component index
+    latency_histories: HashMap<usize, LatencyHistory>,
+    /// number of latency probes sent, to tell their results apart
+    latency_probes: u64,
+    /// histories of each `Command` component, keyed by the component index
+    command_histories: HashMap<usize, CommandHistory>,
+    /// number of programs run, to tell their results apart
+    command_runs: u64,
+    /// histories of each `Battery` component, keyed by the component index
+    battery_histories: HashMap<usize, BatteryHistory>,
+}
+
+/// Histories of a component sampled per instance
+trait InstanceHistory {
+    fn with_capacity(capacity: usize) -> Self;
+
+    fn resize(&mut self, capacity: usize);
+}
+
+impl InstanceHistory for History<f32> {
+    fn with_capacity(capacity: usize) -> Self {
+        History::with_capacity(capacity)
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        History::resize(self, capacity);
+    }
+}
+
+struct NetHistory {
     /// amount uploaded between refresh of `sysinfo::Nets`. (DOES NOT STORE RATE)
     upload: History,
     /// amount downloaded between refresh of `sysinfo::Nets`. (DOES NOT STORE RATE)
     download: History,
+}
+
+impl InstanceHistory for NetHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            upload: History::with_capacity(capacity),
+            download: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.upload.resize(capacity);
+        self.download.resize(capacity);
+    }
+}
+
+struct DiskHistory {
     /// amount read between refresh of `sysinfo::Disks`. (DOES NOT STORE RATE)
-    disk_read: History,
+    read: History,
     /// amount written between refresh of `sysinfo::Disks`. (DOES NOT STORE RATE)
-    disk_write: History,
+    write: History,
+    /// percentage of time the busiest device had requests in flight
+    busy: History<f32>,
+    /// reads completed per second
+    read_ops: History,
+    /// writes completed per second
+    write_ops: History,
+    /// average time to complete a request, in milliseconds
+    latency: History<f32>,
+}
+
+impl InstanceHistory for DiskHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            read: History::with_capacity(capacity),
+            write: History::with_capacity(capacity),
+            busy: History::with_capacity(capacity),
+            read_ops: History::with_capacity(capacity),
+            write_ops: History::with_capacity(capacity),
+            latency: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.read.resize(capacity);
+        self.write.resize(capacity);
+        self.busy.resize(capacity);
+        self.read_ops.resize(capacity);
+        self.write_ops.resize(capacity);
+        self.latency.resize(capacity);
+    }
+}
+
+impl DiskHistory {
+    /// Pushes the stats of the devices, from the counters increased in `elapsed`
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn push_stats<'a>(&mut self, deltas: impl Iterator<Item = &'a DiskStat>, elapsed: Duration) {
+        let elapsed_ms = elapsed.as_secs_f32() * 1000.0;
+        let (mut busy, mut total) = (0.0, DiskStat::default());
+        for delta in deltas {
+            busy = f32::max(busy, delta.io_ticks as f32 / elapsed_ms * 100.0);
+            total.reads += delta.reads;
+            total.read_ticks += delta.read_ticks;
+            total.writes += delta.writes;
+            total.write_ticks += delta.write_ticks;
+        }
+        let requests = total.reads + total.writes;
+        let latency = if requests > 0 {
+            (total.read_ticks + total.write_ticks) as f32 / requests as f32
+        } else {
+            0.0
+        };
+        let per_second = |count: u64| (count as f32 / elapsed.as_secs_f32()).round() as u64;
+
+        self.busy.push(busy.min(100.0));
+        self.read_ops.push(per_second(total.reads));
+        self.write_ops.push(per_second(total.writes));
+        self.latency.push(latency);
+    }
+}
+
+struct CpuTimeHistory {
+    user: History<f32>,
+    nice: History<f32>,
+    system: History<f32>,
+    iowait: History<f32>,
+    /// hardware and software interrupts
+    irq: History<f32>,
+    steal: History<f32>,
+    /// previous times, to compute the time spent between refreshes
+    last: Option<CpuTimes>,
+}
+
+impl CpuTimeHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            user: History::with_capacity(capacity),
+            nice: History::with_capacity(capacity),
+            system: History::with_capacity(capacity),
+            iowait: History::with_capacity(capacity),
+            irq: History::with_capacity(capacity),
+            steal: History::with_capacity(capacity),
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.user.resize(capacity);
+        self.nice.resize(capacity);
+        self.system.resize(capacity);
+        self.iowait.resize(capacity);
+        self.irq.resize(capacity);
+        self.steal.resize(capacity);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, times: CpuTimes) {
+        let delta = self
+            .last
+            .map_or_else(CpuTimes::default, |last| times.since(&last));
+        self.last = Some(times);
+
+        let total = delta.total().max(1) as f32;
+        let percentage = |time: u64| time as f32 / total * 100.0;
+        self.user.push(percentage(delta.user));
+        self.nice.push(percentage(delta.nice));
+        self.system.push(percentage(delta.system));
+        self.iowait.push(percentage(delta.iowait));
+        self.irq.push(percentage(delta.irq + delta.softirq));
+        self.steal.push(percentage(delta.steal));
+    }
+
+    fn layers(&self, colors: &CpuTimeColors) -> Vec<(&History<f32>, Color)> {
+        vec![
+            (&self.user, colors.user),
+            (&self.nice, colors.nice),
+            (&self.system, colors.system),
+            (&self.iowait, colors.iowait),
+            (&self.irq, colors.irq),
+            (&self.steal, colors.steal),
+        ]
+    }
+}
+
+/// Swap activity and major page faults, per second
+struct PagingHistory {
+    swap_in: History,
+    swap_out: History,
+    major_faults: History<f32>,
+    /// previous counters and the time they were read, to compute the rates between refreshes
+    last: Option<(Instant, VmStatSample)>,
+}
+
+impl PagingHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            swap_in: History::with_capacity(capacity),
+            swap_out: History::with_capacity(capacity),
+            major_faults: History::with_capacity(capacity),
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.swap_in.resize(capacity);
+        self.swap_out.resize(capacity);
+        self.major_faults.resize(capacity);
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn push(&mut self, sample: Option<VmStatSample>) {
+        let now = Instant::now();
+        let (swap_in, swap_out, major_faults) = match (sample, self.last) {
+            (Some(sample), Some((time, last))) => {
+                let elapsed = now.duration_since(time).as_secs_f32();
+                let rate = |count: u64, last: u64| count.saturating_sub(last) as f32 / elapsed;
+                (
+                    rate(sample.swap_in, last.swap_in).round() as u64,
+                    rate(sample.swap_out, last.swap_out).round() as u64,
+                    rate(sample.major_faults, last.major_faults),
+                )
+            }
+            _ => (0, 0, 0.0),
+        };
+        self.last = sample.map(|sample| (now, sample));
+
+        self.swap_in.push(swap_in);
+        self.swap_out.push(swap_out);
+        self.major_faults.push(major_faults);
+    }
+}
+
+/// RAM split by usage, in bytes
+struct MemoryHistory {
+    used: History<f32>,
+    shmem: History<f32>,
+    dirty: History<f32>,
+    buffers: History<f32>,
+    cache: History<f32>,
+}
+
+impl MemoryHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            used: History::with_capacity(capacity),
+            shmem: History::with_capacity(capacity),
+            dirty: History::with_capacity(capacity),
+            buffers: History::with_capacity(capacity),
+            cache: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.used.resize(capacity);
+        self.shmem.resize(capacity);
+        self.dirty.resize(capacity);
+        self.buffers.resize(capacity);
+        self.cache.resize(capacity);
+    }
+
+    /// Splits the RAM so that `used`, `shmem` and `dirty` add up to the memory that isn't available
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, sample: MemInfoSample) {
+        let dirty = sample.dirty + sample.writeback;
+        let unavailable = sample.total.saturating_sub(sample.available);
+        let used = unavailable.saturating_sub(sample.shmem + dirty);
+        let cache = sample.cached.saturating_sub(sample.shmem + dirty);
+        self.used.push(used as f32);
+        self.shmem.push(sample.shmem as f32);
+        self.dirty.push(dirty as f32);
+        self.buffers.push(sample.buffers as f32);
+        self.cache.push(cache as f32);
+    }
+
+    fn layers(&self, colors: &MemoryColors) -> Vec<(&History<f32>, Color)> {
+        vec![
+            (&self.used, colors.used),
+            (&self.shmem, colors.shmem),
+            (&self.dirty, colors.dirty),
+            (&self.buffers, colors.buffers),
+            (&self.cache, colors.cache),
+        ]
+    }
+}
+
+struct LoadHistory {
+    one: History<f32>,
+    five: History<f32>,
+    fifteen: History<f32>,
+}
+
+impl LoadHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            one: History::with_capacity(capacity),
+            five: History::with_capacity(capacity),
+            fifteen: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.one.resize(capacity);
+        self.five.resize(capacity);
+        self.fifteen.resize(capacity);
+    }
+
+    fn get(&self, average: LoadAverage) -> &History<f32> {
+        match average {
+            LoadAverage::One => &self.one,
+            LoadAverage::Five => &self.five,
+            LoadAverage::Fifteen => &self.fifteen,
+        }
+    }
+}
+
+struct RateHistory {
+    context_switches: History,
+    interrupts: History,
+    forks: History,
+    /// previous sample and the time it was read, to compute the rates between refreshes
+    last: Option<(Instant, StatSample)>,
+}
+
+impl RateHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            context_switches: History::with_capacity(capacity),
+            interrupts: History::with_capacity(capacity),
+            forks: History::with_capacity(capacity),
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.context_switches.resize(capacity);
+        self.interrupts.resize(capacity);
+        self.forks.resize(capacity);
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn push(&mut self, sample: Option<StatSample>) {
+        let now = Instant::now();
+        let (context_switches, interrupts, forks) = match (sample, self.last) {
+            (Some(sample), Some((time, last))) => {
+                let elapsed = now.duration_since(time).as_secs_f32();
+                let rate = |count: u64, last: u64| {
+                    (count.saturating_sub(last) as f32 / elapsed).round() as u64
+                };
+                (
+                    rate(sample.context_switches, last.context_switches),
+                    rate(sample.interrupts, last.interrupts),
+                    rate(sample.forks, last.forks),
+                )
+            }
+            _ => (0, 0, 0),
+        };
+        self.last = sample.map(|sample| (now, sample));
+
+        self.context_switches.push(context_switches);
+        self.interrupts.push(interrupts);
+        self.forks.push(forks);
+    }
+
+    fn get(&self, rate: StatRate) -> &History {
+        match rate {
+            StatRate::ContextSwitches => &self.context_switches,
+            StatRate::Interrupts => &self.interrupts,
+            StatRate::Forks => &self.forks,
+        }
+    }
+}
+
+struct ProcessHistory {
+    processes: History,
+    threads: History,
+    zombies: History,
+}
+
+impl ProcessHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            processes: History::with_capacity(capacity),
+            threads: History::with_capacity(capacity),
+            zombies: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.processes.resize(capacity);
+        self.threads.resize(capacity);
+        self.zombies.resize(capacity);
+    }
+
+    fn push(&mut self, sample: TaskSample) {
+        self.processes.push(sample.processes);
+        self.threads.push(sample.threads);
+        self.zombies.push(sample.zombies);
+    }
+
+    fn get(&self, count: ProcessCount) -> &History {
+        match count {
+            ProcessCount::Processes => &self.processes,
+            ProcessCount::Threads => &self.threads,
+            ProcessCount::Zombies => &self.zombies,
+        }
+    }
+}
+
+struct SocketHistory {
+    established: History,
+    listen: History,
+    time_wait: History,
+    total: History,
+}
+
+impl SocketHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            established: History::with_capacity(capacity),
+            listen: History::with_capacity(capacity),
+            time_wait: History::with_capacity(capacity),
+            total: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.established.resize(capacity);
+        self.listen.resize(capacity);
+        self.time_wait.resize(capacity);
+        self.total.resize(capacity);
+    }
+
+    fn push(&mut self, sample: SocketSample) {
+        self.established.push(sample.established);
+        self.listen.push(sample.listen);
+        self.time_wait.push(sample.time_wait);
+        self.total.push(sample.total);
+    }
+
+    fn get(&self, count: SocketCount) -> &History {
+        match count {
+            SocketCount::Established => &self.established,
+            SocketCount::Listen => &self.listen,
+            SocketCount::TimeWait => &self.time_wait,
+            SocketCount::Total => &self.total,
+        }
+    }
+}
+
+struct PsiHistory {
+    /// percentage of time in which at least one task was stalled
+    some: History<f32>,
+    /// percentage of time in which every non-idle task was stalled
+    full: History<f32>,
+    /// previous sample and the time it was read, to compute the increase of the totals
+    last: Option<(Instant, PressureSample)>,
+}
+
+impl InstanceHistory for PsiHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            some: History::with_capacity(capacity),
+            full: History::with_capacity(capacity),
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.some.resize(capacity);
+        self.full.resize(capacity);
+    }
+}
+
+impl PsiHistory {
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, sample: Option<PressureSample>, value: PsiValue) {
+        let now = Instant::now();
+        let (some, full) = match (sample, value, self.last) {
+            (Some(sample), PsiValue::Avg10, _) => (
+                sample.some.avg10,
+                sample.full.map_or(0.0, |full| full.avg10),
+            ),
+            (Some(sample), PsiValue::Total, Some((time, last))) => {
+                let elapsed = now.duration_since(time).as_micros() as f32;
+                let percentage = |total: u64, last: u64| {
+                    (total.saturating_sub(last) as f32 / elapsed * 100.0).min(100.0)
+                };
+                (
+                    percentage(sample.some.total, last.some.total),
+                    sample
+                        .full
+                        .zip(last.full)
+                        .map_or(0.0, |(full, last)| percentage(full.total, last.total)),
+                )
+            }
+            _ => (0.0, 0.0),
+        };
+        self.last = sample.map(|sample| (now, sample));
+        self.some.push(some);
+        self.full.push(full);
+    }
+}
+
+struct FanHistory {
+    /// speed, in RPM
+    speed: History<f32>,
+    /// highest `fan*_max` of the fans, in RPM
+    max: Option<f32>,
+}
+
+impl InstanceHistory for FanHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            speed: History::with_capacity(capacity),
+            max: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.speed.resize(capacity);
+    }
+}
+
+struct CgroupHistory {
+    /// percentage of the cpu time of the whole system used by the cgroup
+    cpu: History<f32>,
+    /// memory used by the cgroup
+    memory: History,
+    /// memory limit of the cgroup, or the total memory if it has no limit
+    memory_max: u64,
+    /// previous cpu usage and the time it was read, to compute the cpu used between refreshes
+    last: Option<(Instant, u64)>,
+}
+
+impl InstanceHistory for CgroupHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cpu: History::with_capacity(capacity),
+            memory: History::with_capacity(capacity),
+            memory_max: 0,
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.cpu.resize(capacity);
+        self.memory.resize(capacity);
+    }
+}
+
+impl CgroupHistory {
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, sample: Option<CgroupSample>, cores: usize, total_memory: u64) {
+        let now = Instant::now();
+        let cpu = match (sample, self.last) {
+            (Some(sample), Some((time, last))) => {
+                let elapsed = now.duration_since(time).as_micros() as f32 * cores.max(1) as f32;
+                (sample.cpu_usage.saturating_sub(last) as f32 / elapsed * 100.0).min(100.0)
+            }
+            _ => 0.0,
+        };
+        self.last = sample.map(|sample| (now, sample.cpu_usage));
+
+        self.cpu.push(cpu);
+        self.memory.push(sample.map_or(0, |sample| sample.memory));
+        self.memory_max = sample
+            .and_then(|sample| sample.memory_max)
+            .map_or(total_memory, |max| max.min(total_memory));
+    }
+}
+
+struct WifiHistory {
+    /// link quality, as a percentage of the maximum quality
+    quality: History<f32>,
+    /// signal level, as a percentage between -100 dBm and -50 dBm
+    level: History<f32>,
+    /// latest sample, for the text view and the tooltip
+    last: Option<WirelessSample>,
+}
+
+impl InstanceHistory for WifiHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            quality: History::with_capacity(capacity),
+            level: History::with_capacity(capacity),
+            last: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.quality.resize(capacity);
+        self.level.resize(capacity);
+    }
+}
+
+impl WifiHistory {
+    fn push(&mut self, sample: Option<&WirelessSample>) {
+        self.quality.push(sample.map_or(0.0, |sample| {
+            (sample.quality / wireless::MAX_QUALITY * 100.0).min(100.0)
+        }));
+        self.level.push(sample.map_or(0.0, |sample| {
+            ((sample.level + 100.0) * 2.0).clamp(0.0, 100.0)
+        }));
+        self.last = sample.cloned();
+    }
+
+    fn get(&self, value: WifiValue) -> &History<f32> {
+        match value {
+            WifiValue::Quality => &self.quality,
+            WifiValue::Level => &self.level,
+        }
+    }
+}
+
+struct LatencyHistory {
+    /// round-trip time in milliseconds, or `None` for lost probes
+    rtt: History<Option<f32>>,
+    /// number of probes sent, to ignore the empty history when computing the loss
+    probes: usize,
+    /// address of the target, once resolved
+    address: Option<SocketAddr>,
+    /// token of the probe in flight, whose result is the only one pushed to the history
+    pending: Option<u64>,
+}
+
+impl InstanceHistory for LatencyHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            rtt: History::with_capacity(capacity),
+            probes: 0,
+            address: None,
+            pending: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.rtt.resize(capacity);
+    }
+}
+
+impl LatencyHistory {
+    /// Pushes the result of a probe, where `None` means the target couldn't be resolved
+    fn push(&mut self, sample: Option<LatencySample>) {
+        let rtt = sample.and_then(|sample| sample.rtt);
+        self.rtt.push(rtt.map(|rtt| rtt.as_secs_f32() * 1000.0));
+        self.probes += 1;
+        // the target is resolved again after a lost probe, in case its address changed
+        self.address = sample
+            .filter(|sample| sample.rtt.is_some())
+            .map(|sample| sample.address);
+        self.pending = None;
+    }
+
+    /// Percentage of the probes in the history that were lost
+    #[allow(clippy::cast_precision_loss)]
+    fn loss(&self) -> f32 {
+        let sent = self.probes.min(self.rtt.len());
+        if sent == 0 {
+            return 0.0;
+        }
+        let lost = self
+            .rtt
+            .iter()
+            .rev()
+            .take(sent)
+            .filter(|rtt| rtt.is_none())
+            .count();
+        lost as f32 / sent as f32 * 100.0
+    }
+}
+
+struct CommandHistory {
+    /// values printed by the program, or `None` if it failed
+    value: History<Option<f32>>,
+    /// latest maximum printed by the program
+    max: Option<f32>,
+    /// token of the run in flight, whose result is the only one pushed to the history
+    pending: Option<u64>,
+}
+
+impl InstanceHistory for CommandHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            value: History::with_capacity(capacity),
+            max: None,
+            pending: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.value.resize(capacity);
+    }
+}
+
+impl CommandHistory {
+    fn push(&mut self, sample: Option<CommandSample>) {
+        self.value.push(sample.map(|sample| sample.value));
+        if let Some(sample) = sample {
+            self.max = sample.max;
+        }
+        self.pending = None;
+    }
+}
+
+struct BatteryHistory {
+    /// percentage of the battery charged
+    charge: History<f32>,
+    /// charge or discharge rate of the battery, in watts
+    power: History<f32>,
+    /// power draw of the CPU packages, in watts
+    package: History<f32>,
+    /// previous RAPL counters and the time they were read, to compute the power draw
+    last_energy: Option<(Instant, Vec<EnergyCounter>)>,
+}
+
+impl InstanceHistory for BatteryHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            charge: History::with_capacity(capacity),
+            power: History::with_capacity(capacity),
+            package: History::with_capacity(capacity),
+            last_energy: None,
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.charge.resize(capacity);
+        self.power.resize(capacity);
+        self.package.resize(capacity);
+    }
+}
+
+impl BatteryHistory {
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, battery: Option<&Battery>, packages: &[EnergyCounter]) {
+        let now = Instant::now();
+        let package = self.last_energy.as_ref().map_or(0.0, |(time, last)| {
+            let energy: u64 = packages
+                .iter()
+                .filter_map(|counter| {
+                    let previous = last.iter().find(|previous| previous.zone == counter.zone)?;
+                    Some(counter.since(previous))
+                })
+                .sum();
+            // µJ per µs is J per s
+            energy as f32 / now.duration_since(*time).as_micros() as f32
+        });
+        self.last_energy = Some((now, packages.to_vec()));
+
+        self.charge
+            .push(battery.map_or(0.0, |battery| battery.charge));
+        self.power
+            .push(battery.map_or(0.0, |battery| battery.power));
+        self.package.push(package);
+    }
+
+    /// Whether the power draw of the CPU packages could be read, since the RAPL counters are usually only readable by
+    /// root
+    fn has_packages(&self) -> bool {
+        self.last_energy
+            .as_ref()
+            .is_some_and(|(_, packages)| !packages.is_empty())
+    }
+}
+
+struct GpuHistory {
+    /// percentage of the GPU used
+    usage: History<f32>,
+    /// amount of VRAM used
+    vram: History,
+    temperature: History<f32>,
+    /// power draw, in watts
+    power: History<f32>,
+}
+
+impl InstanceHistory for GpuHistory {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            usage: History::with_capacity(capacity),
+            vram: History::with_capacity(capacity),
+            temperature: History::with_capacity(capacity),
+            power: History::with_capacity(capacity),
+        }
+    }
+
+    fn resize(&mut self, capacity: usize) {
+        self.usage.resize(capacity);
+        self.vram.resize(capacity);
+        self.temperature.resize(capacity);
+        self.power.resize(capacity);
+    }
+}
+
+impl GpuHistory {
+    fn push(&mut self, sample: Option<&GpuSample>) {
+        self.usage
+            .push(sample.and_then(|sample| sample.usage).unwrap_or_default());
+        self.vram.push(
+            sample
+                .and_then(|sample| sample.vram_used)
+                .unwrap_or_default(),
+        );
+        self.temperature.push(
+            sample
+                .and_then(|sample| sample.temperature)
+                .unwrap_or_default(),
+        );
+        self.power
+            .push(sample.and_then(|sample| sample.power).unwrap_or_default());
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     Config(Config),
+    TogglePopup,
+    PopupClosed(window::Id),
+    TickProcesses,
     TickCpu,
     TickMem,
     TickNet,
     TickDisk,
-    // TickGpu,
+    TickFs,
+    TickLoad,
+    TickPsi,
+    TickBattery,
+    TickFan,
+    TickCgroup,
+    TickTcp,
+    TickRate,
+    TickProcessCount,
+    TickWifi,
+    TickLatency,
+    /// Result of the probe sent with a token for the component at an index, or `None` if its target couldn't be
+    /// resolved
+    LatencyProbed(usize, u64, Option<LatencySample>),
+    TickCommand,
+    /// Value printed by the program run with a token for the component at an index, or `None` if it failed
+    CommandOutput(usize, u64, Option<CommandSample>),
+    TickTemp,
+    TickGpu,
 }
 
 #[derive(Clone, Debug)]
@@ -103,14 +1040,956 @@ impl SystemMonitorApplet {
         sized_container(content, size)
     }
 
-    fn aspect_ratio_container_with_padding<'a>(
+    fn aspect_ratio_container_with_padding<'a>(
+        &self,
+        content: impl Into<Element<'a, Message>>,
+        aspect_ratio: f32,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        let size = self.size_aspect_ratio(aspect_ratio);
+
+        sized_container(content, size).padding(padding::top(size.height / 5.0).bottom(0.0))
+    }
+
+    /// Creates the applet, with the backends the GPUs are sampled from
+    fn new(core: Core, flags: Flags, gpu_backends: Vec<Box<dyn GpuBackend>>) -> Self {
+        let (mut cpu, mut mem, mut load, mut tcp, mut rate, mut processes) = Default::default();
+        let sampling = &flags.config.sampling;
+        for chart_config in &flags.config.components {
+            match chart_config {
+                ComponentConfig::Cpu { .. } => cpu = Some(sampling.cpu.sampling_window),
+                ComponentConfig::Mem { .. } => mem = Some(sampling.mem.sampling_window),
+                ComponentConfig::Load { .. } => load = Some(sampling.load.sampling_window),
+                ComponentConfig::Tcp(_) => tcp = Some(sampling.tcp.sampling_window),
+                ComponentConfig::Rate(_) => rate = Some(sampling.rate.sampling_window),
+                ComponentConfig::Processes { .. } => {
+                    processes = Some(sampling.processes.sampling_window);
+                }
+                ComponentConfig::Net { .. }
+                | ComponentConfig::Disk { .. }
+                | ComponentConfig::Gpu { .. }
+                | ComponentConfig::Temp { .. }
+                | ComponentConfig::Fs { .. }
+                | ComponentConfig::Psi { .. }
+                | ComponentConfig::Battery { .. }
+                | ComponentConfig::Fan { .. }
+                | ComponentConfig::Cgroup { .. }
+                | ComponentConfig::Wifi { .. }
+                | ComponentConfig::Latency { .. }
+                | ComponentConfig::Command { .. } => {}
+            }
+        }
+
+        let sys = System::new_all();
+        let cpufreq = CpuFreq::default();
+        let frequency_limits = sys
+            .cpus()
+            .iter()
+            .map(|cpu| cpufreq.limits(cpu.name()))
+            .collect();
+
+        let mut applet = Self {
+            core,
+            config: flags.config,
+            config_handler: flags.config_handler,
+            popup: None,
+
+            sys,
+            nets: Networks::new_with_refreshed_list(),
+            disks: Disks::new_with_refreshed_list(),
+            blocks: BlockDevices::default(),
+            diskstats: DiskStats::default(),
+            hwmon: Hwmon::default(),
+            meminfo: MemInfo::default(),
+            vmstat: VmStat::default(),
+            cgroups: Cgroups::default(),
+            sockets: Sockets::default(),
+            tasks: Tasks::default(),
+            wireless: Wireless::default(),
+            stat: ProcStat::default(),
+            interrupts: Interrupts::default(),
+            pressure: Pressure::default(),
+            power_supply: PowerSupply::default(),
+            rapl: Rapl::default(),
+            batteries: Vec::new(),
+            gpu_backends,
+            gpus: Vec::new(),
+            last_interrupts: None,
+            busiest_irqs: Vec::new(),
+            last_diskstats: None,
+
+            frequency_limits,
+            global_cpu: History::with_capacity(cpu.unwrap_or(0)),
+            core_usages: Vec::new(),
+            cpu_times: CpuTimeHistory::with_capacity(cpu.unwrap_or(0)),
+            frequency: History::with_capacity(cpu.unwrap_or(0)),
+            ram: History::with_capacity(mem.unwrap_or(0)),
+            swap: History::with_capacity(mem.unwrap_or(0)),
+            memory: MemoryHistory::with_capacity(mem.unwrap_or(0)),
+            paging: PagingHistory::with_capacity(mem.unwrap_or(0)),
+            oom_events: Vec::new(),
+            oom_unseen: 0,
+            load: LoadHistory::with_capacity(load.unwrap_or(0)),
+            tcp: SocketHistory::with_capacity(tcp.unwrap_or(0)),
+            rates: RateHistory::with_capacity(rate.unwrap_or(0)),
+            processes: ProcessHistory::with_capacity(processes.unwrap_or(0)),
+            temps: HashMap::new(),
+            fans: HashMap::new(),
+            gpu_histories: HashMap::new(),
+            net_histories: HashMap::new(),
+            disk_histories: HashMap::new(),
+            psi_histories: HashMap::new(),
+            cgroup_histories: HashMap::new(),
+            wifi_histories: HashMap::new(),
+            latency_histories: HashMap::new(),
+            latency_probes: 0,
+            command_histories: HashMap::new(),
+            command_runs: 0,
+            battery_histories: HashMap::new(),
+        };
+        applet.update_instances(&[]);
+        applet
+    }
+
+    /// Updates the histories of the components sampled per instance, from the components of the `previous` config
+    fn update_instances(&mut self, previous: &[ComponentConfig]) {
+        let components = &self.config.components;
+        let sampling = &self.config.sampling;
+        instances(
+            &mut self.temps,
+            previous,
+            components,
+            sampling.temp.sampling_window,
+            |component| matches!(component, ComponentConfig::Temp { .. }),
+        );
+        instances(
+            &mut self.fans,
+            previous,
+            components,
+            sampling.fan.sampling_window,
+            |component| matches!(component, ComponentConfig::Fan { .. }),
+        );
+        instances(
+            &mut self.gpu_histories,
+            previous,
+            components,
+            sampling.gpu.sampling_window,
+            |component| matches!(component, ComponentConfig::Gpu { .. }),
+        );
+        instances(
+            &mut self.net_histories,
+            previous,
+            components,
+            sampling.net.sampling_window,
+            |component| matches!(component, ComponentConfig::Net { .. }),
+        );
+        instances(
+            &mut self.disk_histories,
+            previous,
+            components,
+            sampling.disk.sampling_window,
+            |component| matches!(component, ComponentConfig::Disk { .. }),
+        );
+        instances(
+            &mut self.psi_histories,
+            previous,
+            components,
+            sampling.psi.sampling_window,
+            |component| matches!(component, ComponentConfig::Psi { .. }),
+        );
+        instances(
+            &mut self.cgroup_histories,
+            previous,
+            components,
+            sampling.cgroup.sampling_window,
+            |component| matches!(component, ComponentConfig::Cgroup { .. }),
+        );
+        instances(
+            &mut self.wifi_histories,
+            previous,
+            components,
+            sampling.wifi.sampling_window,
+            |component| matches!(component, ComponentConfig::Wifi { .. }),
+        );
+        instances(
+            &mut self.latency_histories,
+            previous,
+            components,
+            sampling.latency.sampling_window,
+            |component| matches!(component, ComponentConfig::Latency { .. }),
+        );
+        instances(
+            &mut self.command_histories,
+            previous,
+            components,
+            sampling.command.sampling_window,
+            |component| matches!(component, ComponentConfig::Command { .. }),
+        );
+        instances(
+            &mut self.battery_histories,
+            previous,
+            components,
+            sampling.battery.sampling_window,
+            |component| matches!(component, ComponentConfig::Battery { .. }),
+        );
+    }
+
+    /// Draws a view of a single value, where `max` is shown as 100%
+    fn simple_view<'a>(
+        &self,
+        view: &SimpleView,
+        history: &'a History<f32>,
+        max: f32,
+        unit: &str,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            SimpleView::Run {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(history, max, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            SimpleView::Bar {
+                color,
+                aspect_ratio,
+            } => {
+                let content = PercentageBar::new(
+                    self.is_horizontal(),
+                    percentage(history.latest(), max),
+                    *color,
+                );
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            SimpleView::Text => self.text_container(format!("{:.0}{unit}", history.latest())),
+        }
+    }
+
+    /// Draws the relevant input as the back value and the relevant output as the front value
+    fn io_view<'a>(
+        &self,
+        view: &IoView,
+        back: &'a History,
+        front: &'a History,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            IoView::Run {
+                color_front,
+                color_back,
+                aspect_ratio,
+            } => {
+                let content = SuperimposedHistoryChart {
+                    back: HistoryChart::auto_max(back, *color_back),
+                    front: HistoryChart::auto_max(front, *color_front),
+                };
+                self.aspect_ratio_container_with_padding(content, *aspect_ratio)
+            }
+            IoView::RunBack {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::auto_max(back, *color);
+                self.aspect_ratio_container_with_padding(chart, *aspect_ratio)
+            }
+            IoView::RunFront {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::auto_max(front, *color);
+                self.aspect_ratio_container_with_padding(chart, *aspect_ratio)
+            }
+        }
+    }
+
+    /// Draws a usage percentage as the left/back value and a memory amount as the right/front value
+    fn usage_view<'a>(
+        &self,
+        view: &PercentView,
+        usage: &'a History<f32>,
+        memory: &'a History,
+        memory_total: u64,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            PercentView::Bar {
+                color_left,
+                color_right,
+                spacing,
+                aspect_ratio,
+            } => {
+                let bars = vec![
+                    self.aspect_ratio_container(
+                        PercentageBar::new(self.is_horizontal(), usage.latest(), *color_left),
+                        *aspect_ratio,
+                    ),
+                    self.aspect_ratio_container(
+                        PercentageBar::from_pair(
+                            self.is_horizontal(),
+                            memory.latest(),
+                            memory_total,
+                            *color_right,
+                        ),
+                        *aspect_ratio,
+                    ),
+                ];
+                self.panel_collection(bars, *spacing, 0.0)
+                    .apply(container)
+                    .style(base_background)
+            }
+            PercentView::BarLeft {
+                color,
+                aspect_ratio,
+            } => {
+                let content = PercentageBar::new(self.is_horizontal(), usage.latest(), *color);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::BarRight {
+                color,
+                aspect_ratio,
+            } => {
+                let content = PercentageBar::from_pair(
+                    self.is_horizontal(),
+                    memory.latest(),
+                    memory_total,
+                    *color,
+                );
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::Run {
+                aspect_ratio,
+                color_back,
+                color_front,
+            } => {
+                let content = SuperimposedHistoryChart {
+                    back: HistoryChart::new(usage, 100.0, *color_back),
+                    front: HistoryChart::new(memory, memory_total, *color_front),
+                };
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::RunFront {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(usage, 100.0, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            PercentView::RunBack {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(memory, memory_total, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+        }
+    }
+
+    /// Draws the space used in each filesystem, changing color from `warning` and `critical` percentages of used space
+    #[allow(clippy::cast_precision_loss)]
+    fn fs_view<'a>(
+        &self,
+        view: &FsView,
+        filesystems: &[&Disk],
+        warning: f32,
+        critical: f32,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            FsView::Bar {
+                color,
+                color_warning,
+                color_critical,
+                spacing,
+                aspect_ratio,
+            } => {
+                let bars: Vec<_> = filesystems
+                    .iter()
+                    .map(|disk| {
+                        let total = disk.total_space();
+                        let used = total.saturating_sub(disk.available_space());
+                        let percentage = percentage(used as f32, total as f32);
+                        let color = if percentage >= critical {
+                            color_critical
+                        } else if percentage >= warning {
+                            color_warning
+                        } else {
+                            color
+                        };
+                        self.aspect_ratio_container(
+                            PercentageBar::from_pair(self.is_horizontal(), used, total, *color),
+                            *aspect_ratio,
+                        )
+                    })
+                    .collect();
+                self.panel_collection(bars, *spacing, 0.0)
+                    .apply(container)
+                    .style(base_background)
+            }
+            FsView::Text => {
+                let labels: Vec<_> = filesystems
+                    .iter()
+                    .map(|disk| {
+                        let total = disk.total_space();
+                        let used = total.saturating_sub(disk.available_space());
+                        self.text_container(format!(
+                            "{} {}/{}",
+                            disk.mount_point().display(),
+                            format_bytes(used),
+                            format_bytes(total)
+                        ))
+                    })
+                    .collect();
+                self.panel_collection(labels, self.config.layout.inner_spacing, 0.0)
+                    .apply(container)
+                    .style(base_background)
+            }
+        }
+    }
+
+    fn psi_view<'a>(
+        &self,
+        view: &PercentView,
+        history: &'a PsiHistory,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            PercentView::Bar {
+                color_left,
+                color_right,
+                spacing,
+                aspect_ratio,
+            } => {
+                let bars = vec![
+                    self.aspect_ratio_container(
+                        PercentageBar::new(
+                            self.is_horizontal(),
+                            history.some.latest(),
+                            *color_left,
+                        ),
+                        *aspect_ratio,
+                    ),
+                    self.aspect_ratio_container(
+                        PercentageBar::new(
+                            self.is_horizontal(),
+                            history.full.latest(),
+                            *color_right,
+                        ),
+                        *aspect_ratio,
+                    ),
+                ];
+                self.panel_collection(bars, *spacing, 0.0)
+                    .apply(container)
+                    .style(base_background)
+            }
+            PercentView::BarLeft {
+                color,
+                aspect_ratio,
+            } => {
+                let content =
+                    PercentageBar::new(self.is_horizontal(), history.some.latest(), *color);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::BarRight {
+                color,
+                aspect_ratio,
+            } => {
+                let content =
+                    PercentageBar::new(self.is_horizontal(), history.full.latest(), *color);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::Run {
+                aspect_ratio,
+                color_back,
+                color_front,
+            } => {
+                let content = SuperimposedHistoryChart {
+                    back: HistoryChart::new(&history.some, 100.0, *color_back),
+                    front: HistoryChart::new(&history.full, 100.0, *color_front),
+                };
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            PercentView::RunFront {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(&history.some, 100.0, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            PercentView::RunBack {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(&history.full, 100.0, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+        }
+    }
+
+    /// Draws the charge or discharge rate of the battery behind the power draw of the CPU packages, on the same scale,
+    /// or alone when the power draw of the packages can't be read
+    fn battery_power_view<'a>(
+        &self,
+        view: &IoView,
+        history: &'a BatteryHistory,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        let max = history
+            .power
+            .iter()
+            .chain(history.package.iter())
+            .copied()
+            .fold(0.0, f32::max);
+        match view {
+            IoView::Run {
+                color_back,
+                color_front,
+                aspect_ratio,
+            } if history.has_packages() => {
+                let content = SuperimposedHistoryChart {
+                    back: HistoryChart::new(&history.power, max, *color_back),
+                    front: HistoryChart::new(&history.package, max, *color_front),
+                };
+                self.aspect_ratio_container_with_padding(content, *aspect_ratio)
+            }
+            IoView::Run {
+                color_back,
+                aspect_ratio,
+                ..
+            } => {
+                let chart = SimpleHistoryChart::new(&history.power, max, *color_back);
+                self.aspect_ratio_container_with_padding(chart, *aspect_ratio)
+            }
+            IoView::RunBack {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(&history.power, max, *color);
+                self.aspect_ratio_container_with_padding(chart, *aspect_ratio)
+            }
+            IoView::RunFront {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(&history.package, max, *color);
+                self.aspect_ratio_container_with_padding(chart, *aspect_ratio)
+            }
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn memory_view(&self, view: &MemoryView) -> Container<'_, Message, Theme, Renderer> {
+        let total = self.sys.total_memory() as f32;
+        match view {
+            MemoryView::Run {
+                colors,
+                aspect_ratio,
+            } => {
+                let chart = StackedHistoryChart::new(self.memory.layers(colors), total);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            MemoryView::Bar {
+                colors,
+                aspect_ratio,
+            } => {
+                let segments = self
+                    .memory
+                    .layers(colors)
+                    .into_iter()
+                    .map(|(history, color)| (history.latest() / total * 100.0, color))
+                    .collect();
+                let content = SegmentedBar::new(self.is_horizontal(), segments);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+        }
+    }
+
+    /// Draws the load averages, relative to the number of logical cores if `per_core` is set
+    fn load_view(
+        &self,
+        view: &LoadView,
+        per_core: bool,
+    ) -> Container<'_, Message, Theme, Renderer> {
+        #[allow(clippy::cast_precision_loss)]
+        let cores = self.sys.cpus().len().max(1) as f32;
+        // without scaling, the chart grows with the highest load, but never below one busy core
+        let max = if per_core {
+            cores
+        } else {
+            [&self.load.one, &self.load.five, &self.load.fifteen]
+                .into_iter()
+                .flat_map(History::iter)
+                .copied()
+                .fold(1.0, f32::max)
+        };
+        match view {
+            LoadView::Run {
+                color_back,
+                color_front,
+                aspect_ratio,
+            } => {
+                let content = SuperimposedHistoryChart {
+                    back: HistoryChart::new(&self.load.fifteen, max, *color_back),
+                    front: HistoryChart::new(&self.load.one, max, *color_front),
+                };
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            LoadView::RunAverage {
+                average,
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(self.load.get(*average), max, *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            LoadView::Text => {
+                let averages = [&self.load.one, &self.load.five, &self.load.fifteen]
+                    .map(|history| history.latest());
+                let text = if per_core {
+                    averages.map(|load| format!("{:.0}%", load / cores * 100.0))
+                } else {
+                    averages.map(|load| format!("{load:.2}"))
+                };
+                self.text_container(text.join(" "))
+            }
+        }
+    }
+
+    /// Shows the name of the interface in a tooltip
+    fn wifi_view<'a>(
+        &self,
+        view: &SimpleView,
+        value: WifiValue,
+        history: &'a WifiHistory,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        let content = match (view, &history.last) {
+            (SimpleView::Text, Some(sample)) => self.text_container(format!(
+                "{:.0}% {:.0} dBm",
+                history.quality.latest(),
+                sample.level
+            )),
+            (SimpleView::Text, None) => self.text_container(String::from("-")),
+            _ => self.simple_view(view, history.get(value), 100.0, "%"),
+        };
+        match &history.last {
+            Some(sample) => container(tooltip(
+                content,
+                text::body(sample.interface.as_str()),
+                tooltip::Position::FollowCursor,
+            )),
+            None => content,
+        }
+    }
+
+    fn command_view<'a>(
+        &self,
+        view: &SimpleView,
+        max: Option<f32>,
+        history: &'a CommandHistory,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        // the maximum printed by the program comes first, and the chart grows with the highest value otherwise
+        let max = history
+            .max
+            .or(max)
+            .unwrap_or_else(|| history.value.iter().flatten().copied().fold(0.0, f32::max));
+        match view {
+            SimpleView::Run {
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::new(&history.value, Some(max), *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            SimpleView::Bar {
+                color,
+                aspect_ratio,
+            } => {
+                let percentage = history
+                    .value
+                    .latest()
+                    .map_or(0.0, |value| percentage(value, max));
+                let content = PercentageBar::new(self.is_horizontal(), percentage, *color);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            SimpleView::Text => {
+                let text = match (history.value.latest(), history.max) {
+                    (Some(value), Some(max)) => format!("{value}/{max}"),
+                    (Some(value), None) => value.to_string(),
+                    (None, _) => String::from("-"),
+                };
+                self.text_container(text)
+            }
+        }
+    }
+
+    fn latency_view<'a>(
+        &self,
+        view: &LatencyView,
+        history: &'a LatencyHistory,
+    ) -> Container<'a, Message, Theme, Renderer> {
+        match view {
+            LatencyView::Run {
+                color,
+                aspect_ratio,
+            } => {
+                // the chart grows with the slowest probe in the history
+                let max = history.rtt.iter().flatten().copied().fold(0.0, f32::max);
+                let chart = SimpleHistoryChart::new(&history.rtt, Some(max), *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            LatencyView::BarLoss {
+                color,
+                aspect_ratio,
+            } => {
+                let content = PercentageBar::new(self.is_horizontal(), history.loss(), *color);
+                self.aspect_ratio_container(content, *aspect_ratio)
+            }
+            LatencyView::Text => {
+                let rtt = history
+                    .rtt
+                    .latest()
+                    .map_or_else(|| String::from("-"), |rtt| format!("{rtt:.0} ms"));
+                self.text_container(format!("{rtt} {:.0}%", history.loss()))
+            }
+        }
+    }
+
+    fn process_view(
+        &self,
+        view: &ProcessView,
+        color_zombies: Option<Color>,
+    ) -> Container<'_, Message, Theme, Renderer> {
+        match view {
+            ProcessView::Run {
+                count,
+                color,
+                aspect_ratio,
+            } => {
+                let color = color_zombies
+                    .filter(|_| self.processes.zombies.latest() > 0)
+                    .unwrap_or(*color);
+                let chart = SimpleHistoryChart::auto_max(self.processes.get(*count), color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            ProcessView::Text => {
+                let text = format!(
+                    "P:{} T:{} Z:{}",
+                    format_count(self.processes.processes.latest()),
+                    format_count(self.processes.threads.latest()),
+                    self.processes.zombies.latest()
+                );
+                self.text_container(text)
+            }
+        }
+    }
+
+    fn rate_view(&self, view: &RateView) -> Container<'_, Message, Theme, Renderer> {
+        match view {
+            RateView::Run {
+                rate,
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::auto_max(self.rates.get(*rate), *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            RateView::Text => {
+                let text = format!(
+                    "CS:{} IRQ:{} F:{}",
+                    format_count(self.rates.context_switches.latest()),
+                    format_count(self.rates.interrupts.latest()),
+                    format_count(self.rates.forks.latest())
+                );
+                self.text_container(text)
+            }
+        }
+    }
+
+    fn tcp_view(&self, view: &TcpView) -> Container<'_, Message, Theme, Renderer> {
+        match view {
+            TcpView::Run {
+                count,
+                color,
+                aspect_ratio,
+            } => {
+                let chart = SimpleHistoryChart::auto_max(self.tcp.get(*count), *color);
+                self.aspect_ratio_container(chart, *aspect_ratio)
+            }
+            TcpView::Text => {
+                let text = format!(
+                    "E:{} L:{} TW:{} T:{}",
+                    self.tcp.established.latest(),
+                    self.tcp.listen.latest(),
+                    self.tcp.time_wait.latest(),
+                    self.tcp.total.latest()
+                );
+                self.text_container(text)
+            }
+        }
+    }
+
+    /// Frequency of each core, as a percentage between its minimum and maximum frequencies
+    ///
+    /// Cores without cpufreq limits (e.g. in virtual machines) are scaled against the fastest core.
+    #[allow(clippy::cast_precision_loss)]
+    fn core_frequencies(&self) -> Vec<f32> {
+        let cpus = self.sys.cpus();
+        let fastest = cpus.iter().map(Cpu::frequency).max().unwrap_or_default();
+        cpus.iter()
+            .zip(&self.frequency_limits)
+            .map(|(cpu, limits)| {
+                let (min, max) = limits.unwrap_or((0, fastest));
+                if max <= min {
+                    return 0.0;
+                }
+                (cpu.frequency().clamp(min, max) - min) as f32 / (max - min) as f32 * 100.0
+            })
+            .collect()
+    }
+
+    /// The charge of the battery `name`, followed by the time until it's full or empty (e.g. `85% 2:13`)
+    fn battery_text(&self, name: Option<&str>) -> String {
+        let Some(battery) = battery::find(&self.batteries, name) else {
+            return String::new();
+        };
+        match battery.time_remaining() {
+            Some(time) => {
+                let minutes = time.as_secs() / 60;
+                format!(
+                    "{:.0}% {}:{:02}",
+                    battery.charge,
+                    minutes / 60,
+                    minutes % 60
+                )
+            }
+            None => format!("{:.0}%", battery.charge),
+        }
+    }
+
+    /// The `count` processes with the highest `key`, as rows of their name and `value`
+    fn process_rows<'a, K: PartialOrd>(
+        &self,
+        count: usize,
+        key: impl Fn(&Process) -> K,
+        value: impl Fn(&Process) -> String,
+    ) -> Vec<Element<'a, Message>> {
+        // threads are skipped, in case they were listed by an earlier refresh
+        let mut processes: Vec<_> = self
+            .sys
+            .processes()
+            .values()
+            .filter(|process| process.thread_kind().is_none())
+            .collect();
+        processes.sort_by(|a, b| key(b).partial_cmp(&key(a)).unwrap_or(Ordering::Equal));
+        processes
+            .into_iter()
+            .take(count)
+            .map(|process| {
+                Row::new()
+                    .push(
+                        text::body(process.name().to_string_lossy().into_owned())
+                            .width(Length::Fill),
+                    )
+                    .push(text::body(value(process)))
+                    .spacing(8)
+                    .apply(padded_control)
+                    .into()
+            })
+            .collect()
+    }
+
+    fn interrupt_rows<'a>(&self) -> Vec<Element<'a, Message>> {
+        self.busiest_irqs
+            .iter()
+            .map(|(irq, rate)| {
+                Row::new()
+                    .push(
+                        text::body(format!("{} {}", irq.name, irq.description)).width(Length::Fill),
+                    )
+                    .push(text::body(format!("{}/s", format_count(*rate))))
+                    .spacing(8)
+                    .apply(padded_control)
+                    .into()
+            })
+            .collect()
+    }
+
+    /// Draws the number of processes killed by the OOM killer since the popup was last opened over the corner of `view`
+    fn oom_badge<'a>(
         &self,
-        content: impl Into<Element<'a, Message>>,
-        aspect_ratio: f32,
+        view: Container<'a, Message, Theme, Renderer>,
     ) -> Container<'a, Message, Theme, Renderer> {
-        let size = self.size_aspect_ratio(aspect_ratio);
+        let badge = container(self.text_container(format!("OOM:{}", self.oom_unseen)))
+            .width(Length::Fill)
+            .align_x(Alignment::End);
+        // the view dictates the size of the stack
+        container(Stack::with_children([view.into(), badge.into()]))
+    }
 
-        sized_container(content, size).padding(padding::top(size.height / 5.0).bottom(0.0))
+    /// Lists the time of the OOM kills, followed by how long ago they happened, refreshed along with the processes
+    fn oom_rows<'a>(&self) -> Vec<Element<'a, Message>> {
+        let now = Local::now();
+        self.oom_events
+            .iter()
+            .map(|(time, kills)| {
+                // the date is only shown for the kills of previous days
+                let when = if time.date_naive() == now.date_naive() {
+                    time.format("%H:%M:%S")
+                } else {
+                    time.format("%Y-%m-%d %H:%M")
+                };
+                let ago = format_duration((now - *time).to_std().unwrap_or_default());
+                Row::new()
+                    .push(
+                        text::body(format!("{when} ({})", fl!("time-ago", time = ago)))
+                            .width(Length::Fill),
+                    )
+                    .push(text::body(kills.to_string()))
+                    .spacing(8)
+                    .apply(padded_control)
+                    .into()
+            })
+            .collect()
+    }
+
+    /// Lists the IRQ lines with the most interrupts since the previous refresh
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn refresh_interrupts(&mut self) {
+        let count = self.config.popup.interrupts;
+        if count == 0 {
+            return;
+        }
+        let irqs = self.interrupts.read();
+        let now = Instant::now();
+        if let Some((time, last)) = &self.last_interrupts {
+            let elapsed = now.duration_since(*time).as_secs_f32();
+            let mut rates: Vec<_> = irqs
+                .iter()
+                .filter_map(|irq| {
+                    let previous = last.iter().find(|last| last.name == irq.name)?;
+                    let rate = irq.count.saturating_sub(previous.count) as f32 / elapsed;
+                    Some((irq.clone(), rate.round() as u64))
+                })
+                .collect();
+            rates.sort_by_key(|(_, rate)| Reverse(*rate));
+            rates.truncate(count);
+            self.busiest_irqs = rates;
+        }
+        self.last_interrupts = Some((now, irqs));
+    }
+
+    /// Refreshes the usage of the processes listed in the popup, without their threads, which would otherwise be listed as
+    /// processes of their own
+    fn refresh_processes(&mut self) {
+        self.sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .without_tasks(),
+        );
+    }
+
+    fn text_container<'a>(&self, text: String) -> Container<'a, Message, Theme, Renderer> {
+        container(self.core.applet.text(text)).style(base_background)
     }
 
     fn is_horizontal(&self) -> bool {
@@ -139,6 +2018,186 @@ impl SystemMonitorApplet {
     }
 }
 
+/// Runs `f` on its own thread, for the latency probes and commands which block until they
+/// finish or time out
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Option<T> + Send + 'static,
+) -> Option<T> {
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        _ = sender.send(f());
+    });
+    receiver.await.ok().flatten()
+}
+
+/// Updates the histories of the components matching `filter`, keyed by the component index
+///
+/// The history of a component is kept, and resized to `capacity`, while the component at its index in `previous`
+/// samples the same source, so that changing its views doesn't clear it.
+fn instances<T: InstanceHistory>(
+    histories: &mut HashMap<usize, T>,
+    previous: &[ComponentConfig],
+    components: &[ComponentConfig],
+    capacity: usize,
+    filter: impl Fn(&ComponentConfig) -> bool,
+) {
+    let mut kept = mem::take(histories);
+    *histories = components
+        .iter()
+        .enumerate()
+        .filter(|(_, component)| filter(component))
+        .map(|(i, component)| {
+            let same = previous
+                .get(i)
+                .is_some_and(|previous| same_source(previous, component));
+            let history = match kept.remove(&i).filter(|_| same) {
+                Some(mut history) => {
+                    history.resize(capacity);
+                    history
+                }
+                None => T::with_capacity(capacity),
+            };
+            (i, history)
+        })
+        .collect();
+}
+
+/// Whether `component` samples the same source as `previous` (e.g. the same interfaces, disks or sensors)
+fn same_source(previous: &ComponentConfig, component: &ComponentConfig) -> bool {
+    match (previous, component) {
+        (
+            ComponentConfig::Net {
+                include, exclude, ..
+            },
+            ComponentConfig::Net {
+                include: new_include,
+                exclude: new_exclude,
+                ..
+            },
+        ) => include == new_include && exclude == new_exclude,
+        (
+            ComponentConfig::Disk {
+                devices, mounts, ..
+            },
+            ComponentConfig::Disk {
+                devices: new_devices,
+                mounts: new_mounts,
+                ..
+            },
+        ) => devices == new_devices && mounts == new_mounts,
+        (ComponentConfig::Gpu { card, .. }, ComponentConfig::Gpu { card: new_card, .. }) => {
+            card == new_card
+        }
+        (
+            ComponentConfig::Temp { sensors, .. },
+            ComponentConfig::Temp {
+                sensors: new_sensors,
+                ..
+            },
+        ) => sensors == new_sensors,
+        (ComponentConfig::Fan { fans, .. }, ComponentConfig::Fan { fans: new_fans, .. }) => {
+            fans == new_fans
+        }
+        (
+            ComponentConfig::Psi {
+                resource, value, ..
+            },
+            ComponentConfig::Psi {
+                resource: new_resource,
+                value: new_value,
+                ..
+            },
+        ) => resource == new_resource && value == new_value,
+        (
+            ComponentConfig::Battery { battery, .. },
+            ComponentConfig::Battery {
+                battery: new_battery,
+                ..
+            },
+        ) => battery == new_battery,
+        (ComponentConfig::Cgroup { path, .. }, ComponentConfig::Cgroup { path: new_path, .. }) => {
+            path == new_path
+        }
+        (
+            ComponentConfig::Wifi { interface, .. },
+            ComponentConfig::Wifi {
+                interface: new_interface,
+                ..
+            },
+        ) => interface == new_interface,
+        (
+            ComponentConfig::Latency { target, probe, .. },
+            ComponentConfig::Latency {
+                target: new_target,
+                probe: new_probe,
+                ..
+            },
+        ) => target == new_target && probe == new_probe,
+        (
+            ComponentConfig::Command { command, .. },
+            ComponentConfig::Command {
+                command: new_command,
+                ..
+            },
+        ) => command == new_command,
+        _ => false,
+    }
+}
+
+/// `value` as a percentage of `max`, or 0 without a maximum (e.g. a fan without `fan*_max`, an idle disk)
+fn percentage(value: f32, max: f32) -> f32 {
+    if max > 0.0 {
+        (value / max * 100.0).clamp(0.0, 100.0)
+    } else {
+        0.0
+    }
+}
+
+/// Formats a duration with its largest unit (e.g. `45s`, `12m`, `3h`, `2d`)
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    match seconds {
+        0..60 => format!("{seconds}s"),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/// Formats a count with decimal prefixes (e.g. `950`, `12k`, `1.5M`)
+#[allow(clippy::cast_precision_loss)]
+fn format_count(count: u64) -> String {
+    const UNITS: [&str; 4] = ["", "k", "M", "G"];
+    let mut value = count as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit > 0 && value < 10.0 {
+        format!("{value:.1}{}", UNITS[unit])
+    } else {
+        format!("{value:.0}{}", UNITS[unit])
+    }
+}
+
+/// Formats an amount of bytes with binary prefixes (e.g. `1.5G`, `476G`)
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "P"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit > 0 && value < 10.0 {
+        format!("{value:.1}{}", UNITS[unit])
+    } else {
+        format!("{value:.0}{}", UNITS[unit])
+    }
+}
+
 fn sized_container<'a>(
     content: impl Into<Element<'a, Message>>,
     size: Size,
@@ -167,41 +2226,13 @@ impl Application for SystemMonitorApplet {
     }
 
     fn init(core: Core, flags: Self::Flags) -> (Self, Task<Self::Message>) {
-        let (mut cpu, mut mem, mut net, mut disk) = Default::default();
-        let sampling = &flags.config.sampling;
-        for chart_config in &flags.config.components {
-            match chart_config {
-                ComponentConfig::Cpu { .. } => cpu = Some(sampling.cpu.sampling_window),
-                ComponentConfig::Mem { .. } => mem = Some(sampling.mem.sampling_window),
-                ComponentConfig::Net { .. } => net = Some(sampling.net.sampling_window),
-                ComponentConfig::Disk { .. } => disk = Some(sampling.disk.sampling_window), // ComponentConfig::Gpu  => gpu = Some(flags.config.sampling.gpu.sampling_window),
-            }
-        }
-
-        let app = Self {
-            core,
-            config: flags.config,
-            config_handler: flags.config_handler,
-
-            sys: System::new_all(),
-            nets: Networks::new_with_refreshed_list(),
-            disks: Disks::new_with_refreshed_list(),
-
-            global_cpu: History::with_capacity(cpu.unwrap_or(0)),
-            ram: History::with_capacity(mem.unwrap_or(0)),
-            swap: History::with_capacity(mem.unwrap_or(0)),
-            upload: History::with_capacity(net.unwrap_or(0)),
-            download: History::with_capacity(net.unwrap_or(0)),
-            disk_read: History::with_capacity(disk.unwrap_or(0)),
-            disk_write: History::with_capacity(disk.unwrap_or(0)),
-        };
-
-        (app, Task::none())
+        (Self::new(core, flags, gpu::backends()), Task::none())
     }
 
     #[allow(clippy::too_many_lines)]
     fn view(&self) -> Element<Message> {
-        let item_iter = self.config.components.iter().map(|module| {
+        let components = self.config.components.iter().enumerate();
+        let item_iter = components.map(|(i, module)| {
             match module {
                 ComponentConfig::Cpu(vis) => vis
                     .iter()
@@ -249,9 +2280,60 @@ impl Application for SystemMonitorApplet {
                             let chart = SimpleHistoryChart::new(&self.global_cpu, 100.0, *color);
                             self.aspect_ratio_container(chart, *aspect_ratio)
                         }
+                        CpuView::StackedRun {
+                            colors,
+                            aspect_ratio,
+                        } => {
+                            let chart =
+                                StackedHistoryChart::new(self.cpu_times.layers(colors), 100.0);
+                            self.aspect_ratio_container(chart, *aspect_ratio)
+                        }
+                        CpuView::Heatmap {
+                            color,
+                            aspect_ratio,
+                        } => {
+                            let chart = HeatmapChart::new(&self.core_usages, 100.0, *color);
+                            self.aspect_ratio_container(chart, *aspect_ratio)
+                        }
+                        CpuView::RunFrequency {
+                            color,
+                            aspect_ratio,
+                        } => {
+                            let chart = SimpleHistoryChart::new(&self.frequency, 100.0, *color);
+                            self.aspect_ratio_container(chart, *aspect_ratio)
+                        }
+                        CpuView::BarFrequencies {
+                            color,
+                            spacing,
+                            bar_aspect_ratio,
+                            sorting,
+                        } => {
+                            let mut frequencies = self.core_frequencies();
+
+                            frequencies.sort_by(sorting.method());
+
+                            let bars: Vec<_> = frequencies
+                                .into_iter()
+                                .map(|frequency| {
+                                    self.aspect_ratio_container(
+                                        PercentageBar::new(self.is_horizontal(), frequency, *color),
+                                        *bar_aspect_ratio,
+                                    )
+                                })
+                                .collect();
+
+                            self.panel_collection(bars, *spacing, 0.0)
+                                .apply(container)
+                                .style(base_background)
+                        }
                     })
                     .collect::<Vec<_>>(),
-                ComponentConfig::Mem(vis) => vis
+                ComponentConfig::Mem {
+                    views: vis,
+                    breakdown,
+                    swap_views,
+                    fault_views,
+                } => vis
                     .iter()
                     .map(|v| match v {
                         PercentView::Bar {
@@ -342,76 +2424,214 @@ impl Application for SystemMonitorApplet {
                             self.aspect_ratio_container(swap, *aspect_ratio)
                         }
                     })
-                    .collect(),
-                ComponentConfig::Net(vis) => vis
-                    .iter()
-                    .map(|v| match v {
-                        IoView::Run {
-                            aspect_ratio,
-                            color_front,
-                            color_back,
-                        } => {
-                            let upload = HistoryChart::auto_max(&self.upload, *color_front);
-                            let download = HistoryChart::auto_max(&self.download, *color_back);
-
-                            let content = SuperimposedHistoryChart {
-                                back: upload,
-                                front: download,
-                            };
-
-                            self.aspect_ratio_container_with_padding(content, *aspect_ratio)
-                        }
-                        IoView::RunBack {
-                            color,
-                            aspect_ratio,
-                        } => {
-                            let down = SimpleHistoryChart::auto_max(&self.download, *color);
-
-                            self.aspect_ratio_container_with_padding(down, *aspect_ratio)
-                        }
-                        IoView::RunFront {
-                            color,
-                            aspect_ratio,
-                        } => {
-                            let up = SimpleHistoryChart::auto_max(&self.upload, *color);
-                            self.aspect_ratio_container_with_padding(up, *aspect_ratio)
+                    .chain(breakdown.iter().map(|v| self.memory_view(v)))
+                    .chain(
+                        swap_views
+                            .iter()
+                            .map(|v| self.io_view(v, &self.paging.swap_in, &self.paging.swap_out)),
+                    )
+                    .chain(fault_views.iter().map(|v| {
+                        // the chart grows with the highest rate, but never below 1 fault per second
+                        let max = self.paging.major_faults.iter().copied().fold(1.0, f32::max);
+                        self.simple_view(v, &self.paging.major_faults, max, "/s")
+                    }))
+                    // the OOM kills since the popup was last opened are shown over the first view
+                    .enumerate()
+                    .map(|(index, element)| {
+                        if index == 0 && self.oom_unseen > 0 {
+                            self.oom_badge(element)
+                        } else {
+                            element
                         }
                     })
                     .collect(),
-                ComponentConfig::Disk(vis) => vis
+                ComponentConfig::Net { views, .. } => {
+                    let history = &self.net_histories[&i];
+                    views
+                        .iter()
+                        .map(|v| self.io_view(v, &history.download, &history.upload))
+                        .collect()
+                }
+                ComponentConfig::Disk {
+                    views,
+                    busy_views,
+                    iops_views,
+                    latency_views,
+                    ..
+                } => {
+                    let history = &self.disk_histories[&i];
+                    // the latency chart grows with the slowest average in the history
+                    let max_latency = history.latency.iter().copied().fold(0.0, f32::max);
+                    views
+                        .iter()
+                        .map(|v| self.io_view(v, &history.read, &history.write))
+                        .chain(
+                            busy_views
+                                .iter()
+                                .map(|v| self.simple_view(v, &history.busy, 100.0, "%")),
+                        )
+                        .chain(
+                            iops_views
+                                .iter()
+                                .map(|v| self.io_view(v, &history.read_ops, &history.write_ops)),
+                        )
+                        .chain(latency_views.iter().map(|v| match v {
+                            SimpleView::Text => {
+                                self.text_container(format!("{:.1} ms", history.latency.latest()))
+                            }
+                            _ => self.simple_view(v, &history.latency, max_latency, " ms"),
+                        }))
+                        .collect()
+                }
+                ComponentConfig::Temp {
+                    max_temp, views, ..
+                } => views
                     .iter()
-                    .map(|v| match v {
-                        IoView::Run {
-                            color_front,
-                            color_back,
-                            aspect_ratio,
-                        } => {
-                            let read = HistoryChart::auto_max(&self.disk_read, *color_back);
-                            let write = HistoryChart::auto_max(&self.disk_write, *color_front);
-
-                            let content = SuperimposedHistoryChart {
-                                back: read,
-                                front: write,
-                            };
-                            self.aspect_ratio_container_with_padding(content, *aspect_ratio)
-                        }
-                        IoView::RunBack {
-                            color,
-                            aspect_ratio,
-                        } => {
-                            let read = SimpleHistoryChart::auto_max(&self.disk_read, *color);
-                            self.aspect_ratio_container_with_padding(read, *aspect_ratio)
-                        }
-                        IoView::RunFront {
-                            color,
-                            aspect_ratio,
-                        } => {
-                            let write = SimpleHistoryChart::auto_max(&self.disk_write, *color);
-                            self.aspect_ratio_container_with_padding(write, *aspect_ratio)
+                    .map(|v| self.simple_view(v, &self.temps[&i], *max_temp, "°C"))
+                    .collect(),
+                ComponentConfig::Gpu {
+                    card,
+                    views,
+                    temp_views,
+                    max_temp,
+                    power_views,
+                } => {
+                    match gpu::find(&self.gpus, card.as_deref()) {
+                        // nothing to show until the card is found
+                        None => Vec::new(),
+                        Some(sample) => {
+                            let history = &self.gpu_histories[&i];
+                            let power_limit = sample.power_limit.unwrap_or_else(|| {
+                                history.power.iter().copied().fold(0.0, f32::max)
+                            });
+                            views
+                                .iter()
+                                .map(|v| {
+                                    self.usage_view(
+                                        v,
+                                        &history.usage,
+                                        &history.vram,
+                                        sample.vram_total.unwrap_or_default(),
+                                    )
+                                })
+                                .chain(temp_views.iter().map(|v| {
+                                    self.simple_view(v, &history.temperature, *max_temp, "°C")
+                                }))
+                                .chain(power_views.iter().map(|v| {
+                                    self.simple_view(v, &history.power, power_limit, " W")
+                                }))
+                                .collect()
                         }
-                    })
+                    }
+                }
+                ComponentConfig::Fs {
+                    mounts,
+                    views,
+                    warning,
+                    critical,
+                } => {
+                    let filesystems: Vec<_> = mounts
+                        .iter()
+                        .filter_map(|mount| {
+                            self.disks
+                                .iter()
+                                .find(|disk| disk.mount_point() == Path::new(mount))
+                        })
+                        .collect();
+                    views
+                        .iter()
+                        .map(|v| self.fs_view(v, &filesystems, *warning, *critical))
+                        .collect()
+                }
+                ComponentConfig::Load { per_core, views } => {
+                    views.iter().map(|v| self.load_view(v, *per_core)).collect()
+                }
+                ComponentConfig::Tcp(views) => views.iter().map(|v| self.tcp_view(v)).collect(),
+                ComponentConfig::Rate(views) => views.iter().map(|v| self.rate_view(v)).collect(),
+                ComponentConfig::Processes {
+                    color_zombies,
+                    views,
+                } => views
+                    .iter()
+                    .map(|v| self.process_view(v, *color_zombies))
                     .collect(),
-                // ComponentConfig::Disk (vis) => todo!(),
+                ComponentConfig::Psi { views, .. } => {
+                    let history = &self.psi_histories[&i];
+                    views.iter().map(|v| self.psi_view(v, history)).collect()
+                }
+                ComponentConfig::Battery {
+                    battery,
+                    charge_views,
+                    power_views,
+                } => {
+                    let history = &self.battery_histories[&i];
+                    // the charge is hidden on machines without a battery, but RAPL can still be shown
+                    let charge_views =
+                        if battery::find(&self.batteries, battery.as_deref()).is_some() {
+                            &charge_views[..]
+                        } else {
+                            &[]
+                        };
+                    charge_views
+                        .iter()
+                        .map(|v| match v {
+                            SimpleView::Text => {
+                                self.text_container(self.battery_text(battery.as_deref()))
+                            }
+                            _ => self.simple_view(v, &history.charge, 100.0, "%"),
+                        })
+                        // the power draw of the packages is hidden when the RAPL counters can't be read
+                        .chain(
+                            power_views
+                                .iter()
+                                .filter(|v| {
+                                    history.has_packages() || !matches!(v, IoView::RunFront { .. })
+                                })
+                                .map(|v| self.battery_power_view(v, history)),
+                        )
+                        .collect()
+                }
+                ComponentConfig::Cgroup { views, .. } => {
+                    let history = &self.cgroup_histories[&i];
+                    views
+                        .iter()
+                        .map(|v| {
+                            self.usage_view(v, &history.cpu, &history.memory, history.memory_max)
+                        })
+                        .collect()
+                }
+                ComponentConfig::Wifi { value, views, .. } => {
+                    let history = &self.wifi_histories[&i];
+                    views
+                        .iter()
+                        .map(|v| self.wifi_view(v, *value, history))
+                        .collect()
+                }
+                ComponentConfig::Command { max, views, .. } => {
+                    let history = &self.command_histories[&i];
+                    views
+                        .iter()
+                        .map(|v| self.command_view(v, *max, history))
+                        .collect()
+                }
+                ComponentConfig::Latency { views, .. } => {
+                    let history = &self.latency_histories[&i];
+                    views
+                        .iter()
+                        .map(|v| self.latency_view(v, history))
+                        .collect()
+                }
+                ComponentConfig::Fan { max_rpm, views, .. } => {
+                    let history = &self.fans[&i];
+                    // without a configured or reported maximum, the chart grows with the fastest speed
+                    let max = max_rpm
+                        .or(history.max)
+                        .unwrap_or_else(|| history.speed.iter().copied().fold(0.0, f32::max));
+                    views
+                        .iter()
+                        .map(|v| self.simple_view(v, &history.speed, max, " RPM"))
+                        .collect()
+                }
             }
             .apply(|elements| {
                 self.panel_collection(elements, self.config.layout.inner_spacing, 0.0)
@@ -420,54 +2640,455 @@ impl Application for SystemMonitorApplet {
 
         let items = self.panel_collection(item_iter, self.config.layout.spacing, self.padding());
 
-        self.core.applet.autosize_window(items).into()
+        self.core
+            .applet
+            .autosize_window(mouse_area(items).on_press(Message::TogglePopup))
+            .into()
+    }
+
+    fn view_window(&self, _id: window::Id) -> Element<Message> {
+        let count = self.config.popup.processes;
+        // `cpu_usage` is relative to a single core, as in `top`
+        let by_cpu = self.process_rows(count, Process::cpu_usage, |process| {
+            format!("{:.1}%", process.cpu_usage())
+        });
+        let by_memory = self.process_rows(count, Process::memory, |process| {
+            format_bytes(process.memory())
+        });
+
+        let content = Column::new()
+            .push(padded_control(text::heading(fl!("top-cpu"))))
+            .extend(by_cpu)
+            .push(padded_control(divider::horizontal::default()))
+            .push(padded_control(text::heading(fl!("top-memory"))))
+            .extend(by_memory)
+            .push_maybe((self.config.popup.interrupts > 0).then(|| {
+                Column::new()
+                    .push(padded_control(divider::horizontal::default()))
+                    .push(padded_control(text::heading(fl!("top-interrupts"))))
+                    .extend(self.interrupt_rows())
+            }))
+            .push_maybe((!self.oom_events.is_empty()).then(|| {
+                Column::new()
+                    .push(padded_control(divider::horizontal::default()))
+                    .push(padded_control(text::heading(fl!("oom-kills"))))
+                    .extend(self.oom_rows())
+            }))
+            .padding([8, 0]);
+
+        self.core.applet.popup_container(content).into()
+    }
+
+    fn on_close_requested(&self, id: window::Id) -> Option<Message> {
+        Some(Message::PopupClosed(id))
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Config(config) => {
-                self.config = config;
+                let previous = mem::replace(&mut self.config, config);
                 let sampĺing = &self.config.sampling;
                 self.global_cpu.resize(sampĺing.cpu.sampling_window);
+                for history in &mut self.core_usages {
+                    history.resize(sampĺing.cpu.sampling_window);
+                }
+                self.cpu_times.resize(sampĺing.cpu.sampling_window);
+                self.frequency.resize(sampĺing.cpu.sampling_window);
                 self.ram.resize(sampĺing.mem.sampling_window);
                 self.swap.resize(sampĺing.mem.sampling_window);
-                self.upload.resize(sampĺing.net.sampling_window);
-                self.download.resize(sampĺing.net.sampling_window);
-                self.disk_read.resize(sampĺing.disk.sampling_window);
-                self.disk_write.resize(sampĺing.disk.sampling_window);
-                // self.gpu.resize(sampĺing.cpu.sampling_window);
+                self.memory.resize(sampĺing.mem.sampling_window);
+                self.paging.resize(sampĺing.mem.sampling_window);
+                self.load.resize(sampĺing.load.sampling_window);
+                self.tcp.resize(sampĺing.tcp.sampling_window);
+                self.rates.resize(sampĺing.rate.sampling_window);
+                self.processes.resize(sampĺing.processes.sampling_window);
+                self.update_instances(&previous.components);
+            }
+            Message::TogglePopup => {
+                if let Some(id) = self.popup.take() {
+                    return destroy_popup(id);
+                }
+                self.refresh_processes();
+                // the OOM kills are listed in the popup
+                self.oom_unseen = 0;
+                // the rates are computed from the refreshes while the popup is open
+                self.last_interrupts = None;
+                self.busiest_irqs.clear();
+                self.refresh_interrupts();
+                let id = window::Id::unique();
+                self.popup = Some(id);
+                let mut popup_settings = self.core.applet.get_popup_settings(
+                    self.core.main_window_id().unwrap(),
+                    id,
+                    None,
+                    None,
+                    None,
+                );
+                popup_settings.positioner.size_limits = Limits::NONE
+                    .min_width(300.0)
+                    .max_width(400.0)
+                    .min_height(100.0)
+                    .max_height(1080.0);
+                return get_popup(popup_settings);
+            }
+            Message::PopupClosed(id) => {
+                if self.popup == Some(id) {
+                    self.popup = None;
+                }
+            }
+            Message::TickProcesses => {
+                self.refresh_processes();
+                self.refresh_interrupts();
             }
             Message::TickCpu => {
                 self.sys.refresh_cpu_all();
                 self.global_cpu.push(self.sys.global_cpu_usage());
+                let capacity = self.config.sampling.cpu.sampling_window;
+                let cpus = self.sys.cpus();
+                self.core_usages
+                    .resize_with(cpus.len(), || History::with_capacity(capacity));
+                for (history, cpu) in self.core_usages.iter_mut().zip(cpus) {
+                    history.push(cpu.cpu_usage());
+                }
+                if let Some(stat) = self.stat.read() {
+                    self.cpu_times.push(stat.cpu);
+                }
+                let frequencies = self.core_frequencies();
+                #[allow(clippy::cast_precision_loss)]
+                let average = frequencies.iter().sum::<f32>() / frequencies.len().max(1) as f32;
+                self.frequency.push(average);
             }
             Message::TickMem => {
                 self.sys
                     .refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram().with_swap());
                 self.ram.push(self.sys.used_memory());
                 self.swap.push(self.sys.used_swap());
+                if let Some(sample) = self.meminfo.read() {
+                    self.memory.push(sample);
+                }
+                let vmstat = self.vmstat.read();
+                if let (Some(sample), Some((_, last))) = (vmstat, self.paging.last) {
+                    let kills = sample.oom_kills.saturating_sub(last.oom_kills);
+                    if kills > 0 {
+                        self.oom_events.insert(0, (Local::now(), kills));
+                        self.oom_events.truncate(OOM_EVENTS);
+                        self.oom_unseen += kills;
+                    }
+                }
+                self.paging.push(vmstat);
             }
             Message::TickNet => {
                 self.nets.refresh(true);
-                let (received, transmitted) =
-                    self.nets.iter().fold((0, 0), |(acc_r, acc_t), (_, data)| {
-                        (acc_r + data.received(), acc_t + data.transmitted())
-                    });
-                self.upload.push(transmitted);
-                self.download.push(received);
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Net {
+                        include, exclude, ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    let (received, transmitted) = self
+                        .nets
+                        .iter()
+                        .filter(|(name, _)| filter::matches(name, include, exclude))
+                        .fold((0, 0), |(acc_r, acc_t), (_, data)| {
+                            (acc_r + data.received(), acc_t + data.transmitted())
+                        });
+                    if let Some(history) = self.net_histories.get_mut(&i) {
+                        history.upload.push(transmitted);
+                        history.download.push(received);
+                    }
+                }
             }
             Message::TickDisk => {
                 self.disks.refresh(true);
-                let (read, written) = self
+                let partitions: Vec<_> = self
                     .disks
                     .iter()
-                    .map(Disk::usage)
-                    .fold((0, 0), |(acc_r, acc_w), usage| {
-                        (acc_r + usage.read_bytes, acc_w + usage.written_bytes)
+                    .map(|disk| {
+                        let partition = BlockDevices::kernel_name(disk.name());
+                        let device = self.blocks.disk_of(&partition);
+                        (partition, device, disk)
+                    })
+                    .collect();
+
+                let stats = self.diskstats.read();
+                let now = Instant::now();
+                let (elapsed, deltas) = match self.last_diskstats.take() {
+                    Some((time, last)) => {
+                        let deltas: HashMap<_, _> = stats
+                            .iter()
+                            .filter_map(|stat| {
+                                let previous = last.iter().find(|last| last.name == stat.name)?;
+                                Some((stat.name.as_str(), stat.since(previous)))
+                            })
+                            .collect();
+                        (now.duration_since(time), deltas)
+                    }
+                    // nothing to compare with on the first refresh, so the stats are 0
+                    None => (Duration::from_secs(1), HashMap::new()),
+                };
+
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Disk {
+                        devices, mounts, ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    // partitions mounted more than once (e.g. btrfs subvolumes, bind mounts) are only counted once
+                    let mut counted = HashSet::new();
+                    let matching: Vec<_> = partitions
+                        .iter()
+                        .filter(|(partition, device, disk)| {
+                            (filter::matches(partition, devices, &[])
+                                || filter::matches(device, devices, &[]))
+                                && filter::matches(
+                                    &disk.mount_point().to_string_lossy(),
+                                    mounts,
+                                    &[],
+                                )
+                        })
+                        .filter(|(partition, ..)| counted.insert(partition.as_str()))
+                        .collect();
+                    let (read, written) = matching.iter().map(|(.., disk)| disk.usage()).fold(
+                        (0, 0),
+                        |(acc_r, acc_w), usage| {
+                            (acc_r + usage.read_bytes, acc_w + usage.written_bytes)
+                        },
+                    );
+                    // the stats are read from the disks, since their partitions share their queue
+                    let disks: HashSet<_> = matching
+                        .iter()
+                        .map(|(_, device, _)| device.as_str())
+                        .collect();
+                    if let Some(history) = self.disk_histories.get_mut(&i) {
+                        history.read.push(read);
+                        history.write.push(written);
+                        history
+                            .push_stats(disks.iter().filter_map(|disk| deltas.get(disk)), elapsed);
+                    }
+                }
+                self.last_diskstats = Some((now, stats));
+            }
+            Message::TickFs => {
+                // only the space is refreshed, to keep the I/O of the `Disk` components between their own refreshes
+                self.disks
+                    .refresh_specifics(true, DiskRefreshKind::nothing().with_storage());
+            }
+            Message::TickLoad => {
+                let load = System::load_average();
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    self.load.one.push(load.one as f32);
+                    self.load.five.push(load.five as f32);
+                    self.load.fifteen.push(load.fifteen as f32);
+                }
+            }
+            Message::TickRate => {
+                self.rates.push(self.stat.read());
+            }
+            Message::TickProcessCount => {
+                self.processes.push(self.tasks.read().unwrap_or_default());
+            }
+            Message::TickTcp => {
+                self.tcp.push(self.sockets.read().unwrap_or_default());
+            }
+            Message::TickPsi => {
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Psi {
+                        resource, value, ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    let sample = self.pressure.read(resource.file_name());
+                    if let Some(history) = self.psi_histories.get_mut(&i) {
+                        history.push(sample, *value);
+                    }
+                }
+            }
+            Message::TickBattery => {
+                self.batteries = self.power_supply.batteries();
+                let packages = self.rapl.packages();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Battery { battery, .. } = component else {
+                        continue;
+                    };
+                    if let Some(history) = self.battery_histories.get_mut(&i) {
+                        history.push(
+                            battery::find(&self.batteries, battery.as_deref()),
+                            &packages,
+                        );
+                    }
+                }
+            }
+            Message::TickTemp => {
+                let sensors = self.hwmon.temperatures();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Temp {
+                        sensors: labels, ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    let hottest = sensors
+                        .iter()
+                        .filter(|sensor| labels.iter().any(|label| sensor.matches(label)))
+                        .map(|sensor| sensor.input)
+                        .fold(0.0, f32::max);
+                    if let Some(history) = self.temps.get_mut(&i) {
+                        history.push(hottest);
+                    }
+                }
+            }
+            Message::TickCgroup => {
+                let cores = self.sys.cpus().len();
+                let total_memory = self.sys.total_memory();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Cgroup { path, .. } = component else {
+                        continue;
+                    };
+                    let sample = self.cgroups.read(path);
+                    if let Some(history) = self.cgroup_histories.get_mut(&i) {
+                        history.push(sample, cores, total_memory);
+                    }
+                }
+            }
+            Message::TickWifi => {
+                let interfaces = self.wireless.read();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Wifi { interface, .. } = component else {
+                        continue;
+                    };
+                    if let Some(history) = self.wifi_histories.get_mut(&i) {
+                        history.push(wireless::find(&interfaces, interface.as_deref()));
+                    }
+                }
+            }
+            Message::TickLatency => {
+                let interval = Duration::from_millis(self.config.sampling.latency.update_interval);
+                let mut probes = Vec::new();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Latency {
+                        target,
+                        probe,
+                        timeout,
+                        ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    let Some(history) = self.latency_histories.get_mut(&i) else {
+                        continue;
+                    };
+                    // slow probes would pile up if a new one was sent before the previous one is done
+                    if history.pending.is_some() {
+                        continue;
+                    }
+                    self.latency_probes += 1;
+                    let token = self.latency_probes;
+                    history.pending = Some(token);
+
+                    let timeout = Duration::from_millis(*timeout).min(interval);
+                    let (target, probe, address) = (target.clone(), *probe, history.address);
+                    let sample = run_blocking(move || {
+                        let address = address.or_else(|| latency::resolve(&target, timeout))?;
+                        let rtt = match probe {
+                            LatencyProbe::Tcp => latency::tcp(address, timeout),
+                            LatencyProbe::Icmp => latency::icmp(address.ip(), timeout),
+                        };
+                        Some(LatencySample { address, rtt })
                     });
-                self.disk_read.push(read);
-                self.disk_write.push(written);
-            } // Message::TickGpu => todo!(),
+                    probes.push(cosmic::task::future(async move {
+                        Message::LatencyProbed(i, token, sample.await)
+                    }));
+                }
+                return Task::batch(probes);
+            }
+            Message::LatencyProbed(i, token, sample) => {
+                // probes sent for another component at this index, before a config change, are ignored
+                if let Some(history) = self
+                    .latency_histories
+                    .get_mut(&i)
+                    .filter(|history| history.pending == Some(token))
+                {
+                    history.push(sample);
+                }
+            }
+            Message::TickCommand => {
+                let interval = Duration::from_millis(self.config.sampling.command.update_interval);
+                let mut runs = Vec::new();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Command {
+                        command, timeout, ..
+                    } = component
+                    else {
+                        continue;
+                    };
+                    let Some(history) = self.command_histories.get_mut(&i) else {
+                        continue;
+                    };
+                    // the program isn't run again until the previous run is done
+                    if history.pending.is_some() {
+                        continue;
+                    }
+                    self.command_runs += 1;
+                    let token = self.command_runs;
+                    history.pending = Some(token);
+
+                    // commands longer than the interval would pile up
+                    let timeout = Duration::from_millis(*timeout).min(interval);
+                    let program = command.clone();
+                    let run = run_blocking(move || command::run(&program, timeout));
+                    runs.push(cosmic::task::future(async move {
+                        Message::CommandOutput(i, token, run.await)
+                    }));
+                }
+                return Task::batch(runs);
+            }
+            Message::CommandOutput(i, token, sample) => {
+                // programs run for another component at this index, before a config change, are ignored
+                if let Some(history) = self
+                    .command_histories
+                    .get_mut(&i)
+                    .filter(|history| history.pending == Some(token))
+                {
+                    history.push(sample);
+                }
+            }
+            Message::TickFan => {
+                let sensors = self.hwmon.fans();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Fan { fans: labels, .. } = component else {
+                        continue;
+                    };
+                    let fans: Vec<_> = sensors
+                        .iter()
+                        .filter(|sensor| labels.iter().any(|label| sensor.matches(label)))
+                        .collect();
+                    if let Some(history) = self.fans.get_mut(&i) {
+                        history
+                            .speed
+                            .push(fans.iter().map(|fan| fan.input).fold(0.0, f32::max));
+                        history.max = fans.iter().filter_map(|fan| fan.max).reduce(f32::max);
+                    }
+                }
+            }
+            Message::TickGpu => {
+                self.gpus = self
+                    .gpu_backends
+                    .iter_mut()
+                    .flat_map(|backend| backend.sample())
+                    .collect();
+                for (i, component) in self.config.components.iter().enumerate() {
+                    let ComponentConfig::Gpu { card, .. } = component else {
+                        continue;
+                    };
+                    if let Some(history) = self.gpu_histories.get_mut(&i) {
+                        history.push(gpu::find(&self.gpus, card.as_deref()));
+                    }
+                }
+            }
         }
         Task::none()
     }
@@ -494,15 +3115,75 @@ impl Application for SystemMonitorApplet {
                         Duration::from_millis(sampling.disk.update_interval),
                     )
                     .map(|_| Message::TickDisk),
-                    // ComponentConfig::Gpu { .. } => cosmic::iced::time::every(
-                    //     Duration::from_millis(sampling.gpu.update_interval),
-                    // )
-                    // .map(|_| Message::TickGpu),
+                    ComponentConfig::Fs { .. } => cosmic::iced::time::every(Duration::from_millis(
+                        sampling.disk.update_interval,
+                    ))
+                    .map(|_| Message::TickFs),
+                    ComponentConfig::Load { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.load.update_interval),
+                    )
+                    .map(|_| Message::TickLoad),
+                    ComponentConfig::Psi { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.psi.update_interval),
+                    )
+                    .map(|_| Message::TickPsi),
+                    ComponentConfig::Battery { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.battery.update_interval),
+                    )
+                    .map(|_| Message::TickBattery),
+                    ComponentConfig::Fan { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.fan.update_interval),
+                    )
+                    .map(|_| Message::TickFan),
+                    ComponentConfig::Cgroup { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.cgroup.update_interval),
+                    )
+                    .map(|_| Message::TickCgroup),
+                    ComponentConfig::Tcp(_) => cosmic::iced::time::every(Duration::from_millis(
+                        sampling.tcp.update_interval,
+                    ))
+                    .map(|_| Message::TickTcp),
+                    ComponentConfig::Rate(_) => cosmic::iced::time::every(Duration::from_millis(
+                        sampling.rate.update_interval,
+                    ))
+                    .map(|_| Message::TickRate),
+                    ComponentConfig::Processes { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.processes.update_interval),
+                    )
+                    .map(|_| Message::TickProcessCount),
+                    ComponentConfig::Wifi { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.wifi.update_interval),
+                    )
+                    .map(|_| Message::TickWifi),
+                    ComponentConfig::Latency { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.latency.update_interval),
+                    )
+                    .map(|_| Message::TickLatency),
+                    ComponentConfig::Command { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.command.update_interval),
+                    )
+                    .map(|_| Message::TickCommand),
+                    ComponentConfig::Temp { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.temp.update_interval),
+                    )
+                    .map(|_| Message::TickTemp),
+                    ComponentConfig::Gpu { .. } => cosmic::iced::time::every(
+                        Duration::from_millis(sampling.gpu.update_interval),
+                    )
+                    .map(|_| Message::TickGpu),
                 }
             };
             subs.push(tick);
         }
 
+        // the processes are only listed while the popup is open
+        if self.popup.is_some() {
+            subs.push(
+                cosmic::iced::time::every(Duration::from_millis(sampling.cpu.update_interval))
+                    .map(|_| Message::TickProcesses),
+            );
+        }
+
         subs.push(config_subscription());
 
         Subscription::batch(subs)
@@ -519,3 +3200,162 @@ pub fn base_background(theme: &Theme) -> container::Style {
         ..container::Style::default()
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use super::*;
+
+    /// Backend reporting fixed samples of 2 cards
+    struct FakeGpus;
+
+    impl GpuBackend for FakeGpus {
+        fn sample(&mut self) -> Vec<GpuSample> {
+            vec![
+                GpuSample {
+                    card: "fake0".to_owned(),
+                    usage: Some(10.0),
+                    ..GpuSample::default()
+                },
+                GpuSample {
+                    card: "fake1".to_owned(),
+                    usage: Some(42.0),
+                    vram_used: Some(1 << 30),
+                    vram_total: Some(4 << 30),
+                    temperature: Some(60.0),
+                    power: Some(20.0),
+                    power_limit: Some(80.0),
+                },
+            ]
+        }
+    }
+
+    fn gpu(card: Option<&str>) -> ComponentConfig {
+        ComponentConfig::Gpu {
+            card: card.map(ToOwned::to_owned),
+            views: [].into(),
+            temp_views: [].into(),
+            max_temp: 100.0,
+            power_views: [].into(),
+        }
+    }
+
+    fn applet(components: Vec<ComponentConfig>) -> SystemMonitorApplet {
+        let flags = Flags {
+            config_handler: None,
+            config: Config {
+                components: components.into(),
+                ..Config::default()
+            },
+        };
+        SystemMonitorApplet::new(Core::default(), flags, vec![Box::new(FakeGpus)])
+    }
+
+    fn net(views: &[IoView], include: &[&str]) -> ComponentConfig {
+        ComponentConfig::Net {
+            views: views.into(),
+            include: include.iter().map(|&pattern| pattern.to_owned()).collect(),
+            exclude: [].into(),
+        }
+    }
+
+    #[test]
+    fn config_change_keeps_the_histories_of_the_same_sources() {
+        let mut applet = applet(vec![net(&[], &[]), net(&[], &["wl*"])]);
+        for history in applet.net_histories.values_mut() {
+            history.upload.push(100);
+        }
+
+        let view = IoView::RunFront {
+            color: Color::accent_blue,
+            aspect_ratio: 1.5,
+        };
+        let config = Config {
+            components: [net(&[view], &[]), net(&[], &["en*"])].into(),
+            ..applet.config.clone()
+        };
+        _ = applet.update(Message::Config(config));
+
+        // only the views changed
+        assert_eq!(applet.net_histories[&0].upload.latest(), 100);
+        // the interfaces changed
+        assert_eq!(applet.net_histories[&1].upload.latest(), 0);
+    }
+
+    #[test]
+    fn latency_ticks_wait_for_the_probe_in_flight() {
+        let latency = ComponentConfig::Latency {
+            target: "127.0.0.1:1".to_owned(),
+            probe: LatencyProbe::Tcp,
+            timeout: 100,
+            views: [].into(),
+        };
+        let mut applet = applet(vec![latency]);
+        _ = applet.update(Message::TickLatency);
+        let token = applet.latency_histories[&0].pending.unwrap();
+        _ = applet.update(Message::TickLatency);
+        assert_eq!(applet.latency_histories[&0].pending, Some(token));
+
+        // sent before a config change replaced the component
+        _ = applet.update(Message::LatencyProbed(0, token + 1, None));
+        assert_eq!(applet.latency_histories[&0].probes, 0);
+
+        let sample = LatencySample {
+            address: SocketAddr::from(([127, 0, 0, 1], 1)),
+            rtt: Some(Duration::from_millis(5)),
+        };
+        _ = applet.update(Message::LatencyProbed(0, token, Some(sample)));
+        let history = &applet.latency_histories[&0];
+        assert_eq!(history.probes, 1);
+        assert_eq!(history.pending, None);
+        assert_eq!(history.address, Some(sample.address));
+    }
+
+    #[test]
+    fn command_ticks_wait_for_the_run_in_flight() {
+        let command = ComponentConfig::Command {
+            command: ["true".to_owned()].into(),
+            timeout: 100,
+            max: None,
+            views: [].into(),
+        };
+        let mut applet = applet(vec![command]);
+        _ = applet.update(Message::TickCommand);
+        let token = applet.command_histories[&0].pending.unwrap();
+        _ = applet.update(Message::TickCommand);
+        assert_eq!(applet.command_histories[&0].pending, Some(token));
+
+        // run before a config change replaced the component
+        let sample = CommandSample {
+            value: 3.0,
+            max: Some(4.0),
+        };
+        _ = applet.update(Message::CommandOutput(0, token + 1, Some(sample)));
+        assert_eq!(applet.command_histories[&0].max, None);
+
+        _ = applet.update(Message::CommandOutput(0, token, Some(sample)));
+        let history = &applet.command_histories[&0];
+        assert_eq!(history.value.latest(), Some(3.0));
+        assert_eq!(history.max, Some(4.0));
+        assert_eq!(history.pending, None);
+    }
+
+    #[test]
+    fn gpu_tick_samples_the_selected_card() {
+        let mut applet = applet(vec![gpu(None), gpu(Some("fake1")), gpu(Some("card0"))]);
+        _ = applet.update(Message::TickGpu);
+
+        // the first card is used if none is selected
+        assert_eq!(applet.gpu_histories[&0].usage.latest(), 10.0);
+        assert_eq!(applet.gpu_histories[&0].vram.latest(), 0);
+
+        let history = &applet.gpu_histories[&1];
+        assert_eq!(history.usage.latest(), 42.0);
+        assert_eq!(history.vram.latest(), 1 << 30);
+        assert_eq!(history.temperature.latest(), 60.0);
+        assert_eq!(history.power.latest(), 20.0);
+
+        // missing cards are sampled as 0
+        assert_eq!(applet.gpu_histories[&2].usage.latest(), 0.0);
+    }
+}
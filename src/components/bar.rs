@@ -48,7 +48,12 @@ impl PercentageBar {
 
     #[allow(clippy::cast_precision_loss)]
     pub(crate) fn from_pair(is_horizontal: bool, current: u64, max: u64, color: Color) -> Self {
-        let value = current as f32 / max as f32 * 100.0;
+        // e.g. machines without swap, filesystems reporting no space
+        let value = if max == 0 {
+            0.0
+        } else {
+            current as f32 / max as f32 * 100.0
+        };
         Self::new(is_horizontal, value, color)
     }
 }
@@ -120,7 +125,11 @@ impl VerticalPercentageBar {
 
     #[allow(clippy::cast_precision_loss)]
     pub fn from_pair(current: u64, max: u64, color: Color) -> Self {
-        let value = current as f32 / max as f32 * 100.0;
+        let value = if max == 0 {
+            0.0
+        } else {
+            current as f32 / max as f32 * 100.0
+        };
         Self::new(value, color)
     }
 }
@@ -279,3 +288,109 @@ impl From<HorizontalPercentageBar> for Element<'_, Message> {
         Element::new(value)
     }
 }
+
+/// Bar split in consecutive segments, each filling a percentage of the bar
+pub struct SegmentedBar {
+    is_horizontal: bool,
+    segments: Vec<(f32, Color)>,
+}
+
+impl SegmentedBar {
+    pub(crate) fn new(is_horizontal: bool, segments: Vec<(f32, Color)>) -> Self {
+        Self {
+            is_horizontal,
+            segments,
+        }
+    }
+}
+
+impl Widget<Message, Theme, Renderer> for SegmentedBar {
+    fn size(&self) -> Size<Length> {
+        Size::new(Fill, Fill)
+    }
+
+    fn layout(
+        &self,
+        _tree: &mut Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        let Size { width, height } = Widget::size(self);
+        layout::atomic(limits, width, height)
+    }
+
+    fn draw(
+        &self,
+        _state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let outer_rect = &layout.bounds();
+        let edge_line_thickness = 0.01 * outer_rect.height;
+
+        let mut end = 0.0;
+        for (percentage, color) in &self.segments {
+            let start = end;
+            end = (start + percentage.max(0.0)).min(100.0);
+            if end <= start {
+                continue;
+            }
+
+            // like the percentage bars, segments grow from the bottom in horizontal panels and from the right in vertical panels
+            let (fill_rect, line_rect) = if self.is_horizontal {
+                let fill_rect = Rectangle {
+                    y: outer_rect.y + outer_rect.height * (1.0 - end / 100.0),
+                    height: outer_rect.height * (end - start) / 100.0,
+                    ..*outer_rect
+                };
+                let line_rect = Rectangle {
+                    y: fill_rect.y - edge_line_thickness,
+                    height: edge_line_thickness,
+                    ..*outer_rect
+                };
+                (fill_rect, line_rect)
+            } else {
+                let fill_rect = Rectangle {
+                    x: outer_rect.x + outer_rect.width * (1.0 - end / 100.0),
+                    width: outer_rect.width * (end - start) / 100.0,
+                    ..*outer_rect
+                };
+                let line_rect = Rectangle {
+                    x: fill_rect.x,
+                    width: edge_line_thickness,
+                    ..*outer_rect
+                };
+                (fill_rect, line_rect)
+            };
+
+            let line_color = color.as_cosmic_color(theme);
+            iced::core::Renderer::fill_quad(
+                renderer,
+                renderer::Quad {
+                    bounds: line_rect,
+                    ..renderer::Quad::default()
+                },
+                iced::Color::from(line_color),
+            );
+            iced::core::Renderer::fill_quad(
+                renderer,
+                renderer::Quad {
+                    bounds: fill_rect,
+                    ..renderer::Quad::default()
+                },
+                // make the fill more transparent
+                iced::Color::from(line_color.with_alpha(line_color.alpha / 2.0)),
+            );
+        }
+    }
+}
+
+impl From<SegmentedBar> for Element<'_, Message> {
+    fn from(value: SegmentedBar) -> Self {
+        Element::new(value)
+    }
+}
@@ -1,6 +1,6 @@
 use cosmic::{
     cosmic_theme::palette::WithAlpha,
-    iced::{core::mouse, Point, Rectangle},
+    iced::{core::mouse, Point, Rectangle, Size},
     widget::{
         canvas::{path, stroke, Fill, Frame, Geometry, Program, Stroke},
         Canvas,
@@ -99,6 +99,76 @@ macro_rules! impl_program_history_chart {
 }
 impl_program_history_chart!(u64, f32);
 
+/// Missing samples (e.g. failed probes) are drawn as gaps between the areas of the samples around them
+impl Program<Message, Theme, Renderer> for HistoryChart<'_, Option<f32>> {
+    type State = ();
+
+    #[allow(clippy::cast_precision_loss)]
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let mut fill = Frame::new(renderer, bounds.size());
+        let mut line = Frame::new(renderer, bounds.size());
+        let color = self.color.as_cosmic_color(theme);
+
+        let max = self.max.unwrap_or_default();
+        let x_step = bounds.width / (self.history.len() - 1) as f32;
+        let y_step = if max != 0.0 { bounds.height / max } else { 1.0 };
+        let points: Vec<_> = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(i, sample)| {
+                sample.map(|y| Point {
+                    x: i as f32 * x_step,
+                    y: bounds.height - y * y_step,
+                })
+            })
+            .collect();
+
+        for segment in points.split(Option::is_none) {
+            let (Some(Some(first)), Some(Some(last))) = (segment.first(), segment.last()) else {
+                continue;
+            };
+            let mut path_builder = path::Builder::new();
+            path_builder.move_to(Point {
+                x: first.x,
+                y: bounds.height,
+            });
+            for point in segment.iter().flatten() {
+                path_builder.line_to(*point);
+            }
+            path_builder.line_to(Point {
+                x: last.x,
+                y: bounds.height,
+            });
+
+            let path = path_builder.build();
+            fill.fill(
+                &path,
+                Fill {
+                    style: stroke::Style::Solid(color.with_alpha(0.5).into()),
+                    ..Default::default()
+                },
+            );
+            line.stroke(
+                &path,
+                Stroke {
+                    style: stroke::Style::Solid(color.into()),
+                    width: 1.0,
+                    ..Default::default()
+                },
+            );
+        }
+        vec![fill.into_geometry(), line.into_geometry()]
+    }
+}
+
 #[derive(Debug)]
 pub struct SimpleHistoryChart<'a, T = u64> {
     history: HistoryChart<'a, T>,
@@ -138,7 +208,7 @@ macro_rules! impl_program_simple_history_chart {
         )*
     };
 }
-impl_program_simple_history_chart!(u64, f32);
+impl_program_simple_history_chart!(u64, f32, Option<f32>);
 
 impl<'a> SimpleHistoryChart<'a> {
     pub fn auto_max(history: &'a History, color: Color) -> SimpleHistoryChart<'a> {
@@ -163,18 +233,25 @@ impl<'a, T> SimpleHistoryChart<'a, T> {
 }
 
 #[derive(Debug)]
-pub struct SuperimposedHistoryChart<'a> {
-    pub back: HistoryChart<'a>,
-    pub front: HistoryChart<'a>,
+pub struct SuperimposedHistoryChart<'a, B = u64, F = u64> {
+    pub back: HistoryChart<'a, B>,
+    pub front: HistoryChart<'a, F>,
 }
 
-impl<'a> From<SuperimposedHistoryChart<'a>> for Element<'a, Message> {
-    fn from(value: SuperimposedHistoryChart<'a>) -> Self {
+impl<'a, B: 'a, F: 'a> From<SuperimposedHistoryChart<'a, B, F>> for Element<'a, Message>
+where
+    SuperimposedHistoryChart<'a, B, F>: Program<Message, Theme, Renderer>,
+{
+    fn from(value: SuperimposedHistoryChart<'a, B, F>) -> Self {
         Canvas::new(value).into()
     }
 }
 
-impl Program<Message, Theme, Renderer> for SuperimposedHistoryChart<'_> {
+impl<'a, B, F> Program<Message, Theme, Renderer> for SuperimposedHistoryChart<'a, B, F>
+where
+    HistoryChart<'a, B>: Program<Message, Theme, Renderer, State = ()>,
+    HistoryChart<'a, F>: Program<Message, Theme, Renderer, State = ()>,
+{
     type State = ();
 
     fn draw(
@@ -193,6 +270,166 @@ impl Program<Message, Theme, Renderer> for SuperimposedHistoryChart<'_> {
     }
 }
 
+/// Histories drawn on top of each other, from the first to the last layer
+#[derive(Debug)]
+pub struct StackedHistoryChart<'a> {
+    layers: Vec<(&'a History<f32>, Color)>,
+    max: f32,
+}
+
+impl<'a> StackedHistoryChart<'a> {
+    pub fn new(layers: Vec<(&'a History<f32>, Color)>, max: f32) -> Self {
+        Self { layers, max }
+    }
+}
+
+impl<'a> From<StackedHistoryChart<'a>> for Element<'a, Message> {
+    fn from(value: StackedHistoryChart<'a>) -> Self {
+        Canvas::new(value).into()
+    }
+}
+
+impl Program<Message, Theme, Renderer> for StackedHistoryChart<'_> {
+    type State = ();
+
+    #[allow(clippy::cast_precision_loss)]
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let mut geometries = Background.draw(state, renderer, theme, bounds, cursor);
+        let mut fill = Frame::new(renderer, bounds.size());
+        let mut line = Frame::new(renderer, bounds.size());
+
+        let len = self.layers.first().map_or(0, |(history, _)| history.len());
+        let x_step = bounds.width / len.saturating_sub(1).max(1) as f32;
+        let y_step = if self.max != 0.0 {
+            bounds.height / self.max
+        } else {
+            1.0
+        };
+        let point = |i: usize, value: f32| Point {
+            x: i as f32 * x_step,
+            y: bounds.height - value * y_step,
+        };
+
+        // each layer is filled between its own top and the top of the layers below it
+        let mut bottom = vec![0.0; len];
+        for (history, color) in &self.layers {
+            let color = color.as_cosmic_color(theme);
+            let top: Vec<f32> = bottom
+                .iter()
+                .zip(history.iter())
+                .map(|(bottom, value)| bottom + value)
+                .collect();
+
+            let mut area = path::Builder::new();
+            let mut edge = path::Builder::new();
+            for (i, value) in top.iter().enumerate() {
+                if i == 0 {
+                    area.move_to(point(i, *value));
+                    edge.move_to(point(i, *value));
+                } else {
+                    area.line_to(point(i, *value));
+                    edge.line_to(point(i, *value));
+                }
+            }
+            for (i, value) in bottom.iter().enumerate().rev() {
+                area.line_to(point(i, *value));
+            }
+            area.close();
+
+            fill.fill(
+                &area.build(),
+                Fill {
+                    style: stroke::Style::Solid(color.with_alpha(0.5).into()),
+                    ..Default::default()
+                },
+            );
+            line.stroke(
+                &edge.build(),
+                Stroke {
+                    style: stroke::Style::Solid(color.into()),
+                    width: 1.0,
+                    ..Default::default()
+                },
+            );
+            bottom = top;
+        }
+
+        geometries.extend([fill.into_geometry(), line.into_geometry()]);
+        geometries
+    }
+}
+
+/// One row per history, where each sample is a cell colored by its value
+#[derive(Debug)]
+pub struct HeatmapChart<'a> {
+    rows: &'a [History<f32>],
+    max: f32,
+    color: Color,
+}
+
+impl<'a> HeatmapChart<'a> {
+    pub fn new(rows: &'a [History<f32>], max: f32, color: Color) -> Self {
+        Self { rows, max, color }
+    }
+}
+
+impl<'a> From<HeatmapChart<'a>> for Element<'a, Message> {
+    fn from(value: HeatmapChart<'a>) -> Self {
+        Canvas::new(value).into()
+    }
+}
+
+impl Program<Message, Theme, Renderer> for HeatmapChart<'_> {
+    type State = ();
+
+    #[allow(clippy::cast_precision_loss)]
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<Geometry<Renderer>> {
+        let mut geometries = Background.draw(state, renderer, theme, bounds, cursor);
+        let mut frame = Frame::new(renderer, bounds.size());
+        let color = self.color.as_cosmic_color(theme);
+
+        let cell_height = bounds.height / self.rows.len().max(1) as f32;
+        for (row, history) in self.rows.iter().enumerate() {
+            let cell_width = bounds.width / history.len() as f32;
+            for (column, value) in history.iter().enumerate() {
+                let intensity = if self.max != 0.0 {
+                    (value / self.max).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                frame.fill_rectangle(
+                    Point {
+                        x: column as f32 * cell_width,
+                        y: row as f32 * cell_height,
+                    },
+                    Size::new(cell_width, cell_height),
+                    Fill {
+                        style: stroke::Style::Solid(color.with_alpha(intensity).into()),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        geometries.push(frame.into_geometry());
+        geometries
+    }
+}
+
 struct Background;
 
 impl Program<Message, Theme, Renderer> for Background {
@@ -14,7 +14,7 @@ use crate::{
     color::Color,
     components::bar::SortMethod,
 };
-pub const CONFIG_VERSION: u64 = 2;
+pub const CONFIG_VERSION: u64 = 3;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Config {
@@ -22,6 +22,7 @@ pub struct Config {
     pub sampling: SamplingConfig,
     pub components: Box<[ComponentConfig]>,
     pub layout: LayoutConfig,
+    pub popup: PopupConfig,
 }
 
 impl CosmicConfigEntry for Config {
@@ -31,6 +32,7 @@ impl CosmicConfigEntry for Config {
         ConfigSet::set(&tx, "sampling", &self.sampling)?;
         ConfigSet::set(&tx, "components", &self.components)?;
         ConfigSet::set(&tx, "layout", &self.layout)?;
+        ConfigSet::set(&tx, "popup", &self.popup)?;
         tx.commit()
     }
     fn get_entry(config: &CosmicConfig) -> Result<Self, (Vec<ConfigError>, Self)> {
@@ -56,6 +58,7 @@ impl CosmicConfigEntry for Config {
         config_get!(sampling, SamplingConfig);
         config_get!(components, Box<[ComponentConfig]>);
         config_get!(layout, LayoutConfig);
+        config_get!(popup, PopupConfig);
 
         if errors.is_empty() {
             Ok(default)
@@ -93,6 +96,7 @@ impl CosmicConfigEntry for Config {
                 "sampling" => config_set!(sampling, SamplingConfig),
                 "components" => config_set!(components, Box<[ComponentConfig]>),
                 "layout" => config_set!(layout, LayoutConfig),
+                "popup" => config_set!(popup, PopupConfig),
                 _ => {}
             }
         }
@@ -108,12 +112,34 @@ pub struct LayoutConfig {
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PopupConfig {
+    /// number of processes listed by CPU usage and by memory usage
+    pub processes: usize,
+    /// number of IRQ lines listed by interrupts per second, none are listed if 0
+    pub interrupts: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
 pub struct SamplingConfig {
     pub cpu: Sampling,
     pub mem: Sampling,
     pub net: Sampling,
     pub disk: Sampling,
     pub gpu: Sampling,
+    pub temp: Sampling,
+    pub load: Sampling,
+    pub psi: Sampling,
+    pub battery: Sampling,
+    pub fan: Sampling,
+    pub cgroup: Sampling,
+    pub tcp: Sampling,
+    pub wifi: Sampling,
+    pub latency: Sampling,
+    pub rate: Sampling,
+    pub processes: Sampling,
+    pub command: Sampling,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -136,9 +162,152 @@ pub enum PaddingOption {
 pub enum ComponentConfig {
     Cpu(Box<[CpuView]>),
 
-    Mem(Box<[PercentView]>),
-    Net(Box<[IoView]>),
-    Disk(Box<[IoView]>),
+    Mem {
+        views: Box<[PercentView]>,
+        /// views of the RAM split by usage, read from `/proc/meminfo`
+        #[serde(default)]
+        breakdown: Box<[MemoryView]>,
+        /// views of the pages read from and written to swap per second, read from `/proc/vmstat`
+        #[serde(default)]
+        swap_views: Box<[IoView]>,
+        /// views of the page faults that required reading from disk per second, read from `/proc/vmstat`
+        #[serde(default)]
+        fault_views: Box<[SimpleView]>,
+    },
+    Net {
+        views: Box<[IoView]>,
+        /// glob patterns of the monitored interfaces (e.g. `wlp*`), all interfaces are monitored if empty
+        #[serde(default)]
+        include: Box<[String]>,
+        /// glob patterns of the ignored interfaces (e.g. `docker*`, `veth*`)
+        #[serde(default = "default_net_exclude")]
+        exclude: Box<[String]>,
+    },
+    Disk {
+        views: Box<[IoView]>,
+        /// views of the percentage of time the busiest device had requests in flight, read from `/proc/diskstats`
+        #[serde(default)]
+        busy_views: Box<[SimpleView]>,
+        /// views of the read and write operations per second, read from `/proc/diskstats`
+        #[serde(default)]
+        iops_views: Box<[IoView]>,
+        /// views of the average time to complete a request, read from `/proc/diskstats`
+        #[serde(default)]
+        latency_views: Box<[SimpleView]>,
+        /// glob patterns of the monitored disks or partitions (e.g. `nvme0n1`, `sd*`), all of them are monitored if empty
+        #[serde(default)]
+        devices: Box<[String]>,
+        /// glob patterns of the monitored mount points (e.g. `/run/media/*`), all of them are monitored if empty
+        #[serde(default)]
+        mounts: Box<[String]>,
+    },
+    Gpu {
+        /// card to monitor (e.g. `card1`, `nvidia0`), the first one found is used if not set
+        #[serde(default)]
+        card: Option<String>,
+        views: Box<[PercentView]>,
+        #[serde(default)]
+        temp_views: Box<[SimpleView]>,
+        /// temperature (in degrees Celsius) shown as 100%
+        #[serde(default = "default_max_temp")]
+        max_temp: f32,
+        /// views of the power draw, shown relative to the power limit of the card
+        #[serde(default)]
+        power_views: Box<[SimpleView]>,
+    },
+    Temp {
+        /// labels of the monitored sensors (e.g. `Tctl`, `Package id 0`, `amdgpu edge`), the hottest one is shown
+        sensors: Box<[String]>,
+        /// temperature (in degrees Celsius) shown as 100%
+        max_temp: f32,
+        views: Box<[SimpleView]>,
+    },
+    Fs {
+        /// monitored mount points (e.g. `/`, `/home`), shown in this order
+        mounts: Box<[String]>,
+        views: Box<[FsView]>,
+        /// percentage of used space from which the bars use `color_warning`
+        #[serde(default = "default_fs_warning")]
+        warning: f32,
+        /// percentage of used space from which the bars use `color_critical`
+        #[serde(default = "default_fs_critical")]
+        critical: f32,
+    },
+    Load {
+        /// divide the load by the number of logical cores, so that 100% means every core is busy
+        #[serde(default)]
+        per_core: bool,
+        views: Box<[LoadView]>,
+    },
+    Psi {
+        resource: PsiResource,
+        #[serde(default)]
+        value: PsiValue,
+        views: Box<[PercentView]>,
+    },
+    Battery {
+        /// battery to monitor (e.g. `BAT1`), the first one found is used if not set
+        #[serde(default)]
+        battery: Option<String>,
+        /// views of the charge, the text view also shows the time remaining
+        #[serde(default)]
+        charge_views: Box<[SimpleView]>,
+        /// views of the charge or discharge rate of the battery and the power draw of the CPU packages
+        #[serde(default)]
+        power_views: Box<[IoView]>,
+    },
+    Fan {
+        /// labels of the monitored fans (e.g. `fan1`, `CPU Fan`, `amdgpu fan1`), the fastest one is shown
+        fans: Box<[String]>,
+        /// speed (in RPM) shown as 100%, the highest `fan*_max` of the fans is used if not set
+        #[serde(default)]
+        max_rpm: Option<f32>,
+        views: Box<[SimpleView]>,
+    },
+    Cgroup {
+        /// path of the cgroup, relative to `/sys/fs/cgroup` (e.g. `user.slice`, `system.slice/docker.service`)
+        path: String,
+        views: Box<[PercentView]>,
+    },
+    Tcp(Box<[TcpView]>),
+    Wifi {
+        /// wireless interface to monitor (e.g. `wlan0`), the first one found is used if not set
+        #[serde(default)]
+        interface: Option<String>,
+        /// value shown in the run and bar charts
+        #[serde(default)]
+        value: WifiValue,
+        views: Box<[SimpleView]>,
+    },
+    Latency {
+        /// host probed, with a port for TCP probes (e.g. `example.com:443`)
+        target: String,
+        #[serde(default)]
+        probe: LatencyProbe,
+        /// time (in milliseconds) after which a probe is considered lost, capped at the update interval
+        #[serde(default = "default_latency_timeout")]
+        timeout: u64,
+        views: Box<[LatencyView]>,
+    },
+    Rate(Box<[RateView]>),
+    Processes {
+        /// color of the run charts while there are zombie processes, the colors don't change if not set
+        #[serde(default)]
+        color_zombies: Option<Color>,
+        views: Box<[ProcessView]>,
+    },
+    Command {
+        /// program and its arguments (e.g. `["sh", "-c", "ls ~/Downloads | wc -l"]`), which prints a number or `<value>/<max>`
+        command: Box<[String]>,
+        /// time (in milliseconds) after which the program is killed, capped at the update interval
+        #[serde(default = "default_command_timeout")]
+        timeout: u64,
+        /// value shown as 100% in the run and bar charts when the program doesn't print a maximum,
+        /// the highest value in the history is used if not set
+        #[serde(default)]
+        max: Option<f32>,
+        views: Box<[SimpleView]>,
+    },
 }
 
 pub fn config_subscription() -> Subscription<Message> {
@@ -166,10 +335,20 @@ pub enum IoView {
     #[serde(rename = "RunChart")]
     Run {
         /// The `cosmic::palette` color to represent the relevant input (e.g. input = disk read rate, net download rate)
-        #[serde(alias = "color_read", alias = "color_download")]
+        #[serde(
+            alias = "color_read",
+            alias = "color_download",
+            alias = "color_battery",
+            alias = "color_swap_in"
+        )]
         color_back: Color,
         /// The `cosmic::palette` color to represent the relevant output (e.g. output = disk write rate, net upload rate)
-        #[serde(alias = "color_write", alias = "color_upload")]
+        #[serde(
+            alias = "color_write",
+            alias = "color_upload",
+            alias = "color_package",
+            alias = "color_swap_out"
+        )]
         color_front: Color,
         /// The **ratio** of width to height of the graph.
         aspect_ratio: f32,
@@ -178,14 +357,18 @@ pub enum IoView {
     #[serde(
         rename = "RunChartBack",
         alias = "RunChartRead",
-        alias = "RunChartDownload"
+        alias = "RunChartDownload",
+        alias = "RunChartBattery",
+        alias = "RunChartSwapIn"
     )]
     RunBack { color: Color, aspect_ratio: f32 },
     /// If IO, B is the system output (e.g. output = disk write rate, net upload rate)
     #[serde(
         rename = "RunChartFront",
         alias = "RunChartWrite",
-        alias = "RunChartUpload"
+        alias = "RunChartUpload",
+        alias = "RunChartPackage",
+        alias = "RunChartSwapOut"
     )]
     RunFront { color: Color, aspect_ratio: f32 },
 }
@@ -207,38 +390,410 @@ pub enum CpuView {
         bar_aspect_ratio: f32,
         sorting: SortMethod,
     },
+    /// Time spent by the cpus in each state, stacked in the order of [`CpuTimeColors`]
+    #[serde(rename = "StackedRunChart")]
+    StackedRun {
+        #[serde(default)]
+        colors: CpuTimeColors,
+        aspect_ratio: f32,
+    },
+    /// Usage history of each core, as a row of cells colored by their usage
+    Heatmap {
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// Average frequency of the cores, between their minimum and maximum frequencies
+    #[serde(rename = "RunChartFrequency")]
+    RunFrequency {
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// Frequency of each core, between its minimum and maximum frequencies
+    BarFrequencies {
+        color: Color,
+        spacing: f32,
+        bar_aspect_ratio: f32,
+        sorting: SortMethod,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CpuTimeColors {
+    pub user: Color,
+    /// user time of processes with a positive niceness
+    pub nice: Color,
+    pub system: Color,
+    /// idle time waiting for I/O
+    pub iowait: Color,
+    /// time handling hardware and software interrupts
+    pub irq: Color,
+    /// time taken by the hypervisor for other virtual machines
+    pub steal: Color,
+}
+
+impl Default for CpuTimeColors {
+    fn default() -> Self {
+        Self {
+            user: Color::accent_blue,
+            nice: Color::accent_green,
+            system: Color::accent_red,
+            iowait: Color::accent_orange,
+            irq: Color::accent_purple,
+            steal: Color::accent_warm_grey,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum PercentView {
     #[serde(rename = "RunChart")]
     Run {
-        #[serde(alias = "color_ram")]
+        #[serde(
+            alias = "color_ram",
+            alias = "color_usage",
+            alias = "color_some",
+            alias = "color_cpu"
+        )]
         color_back: Color,
-        #[serde(alias = "color_swap")]
+        #[serde(
+            alias = "color_swap",
+            alias = "color_vram",
+            alias = "color_full",
+            alias = "color_memory"
+        )]
         color_front: Color,
         aspect_ratio: f32,
     },
-    #[serde(rename = "RunChartBack", alias = "RunChartRam")]
+    #[serde(
+        rename = "RunChartBack",
+        alias = "RunChartRam",
+        alias = "RunChartUsage",
+        alias = "RunChartSome",
+        alias = "RunChartCpu"
+    )]
     RunFront { color: Color, aspect_ratio: f32 },
-    #[serde(rename = "RunChartFront", alias = "RunChartSwap")]
+    #[serde(
+        rename = "RunChartFront",
+        alias = "RunChartSwap",
+        alias = "RunChartVram",
+        alias = "RunChartFull",
+        alias = "RunChartMemory"
+    )]
     RunBack { color: Color, aspect_ratio: f32 },
 
     #[serde(rename = "BarChart")]
     Bar {
-        #[serde(alias = "color_ram")]
+        #[serde(
+            alias = "color_ram",
+            alias = "color_usage",
+            alias = "color_some",
+            alias = "color_cpu"
+        )]
         color_left: Color,
-        #[serde(alias = "color_swap")]
+        #[serde(
+            alias = "color_swap",
+            alias = "color_vram",
+            alias = "color_full",
+            alias = "color_memory"
+        )]
         color_right: Color,
         spacing: f32,
         aspect_ratio: f32,
     },
-    #[serde(alias = "BarChartRam")]
+    #[serde(
+        alias = "BarChartRam",
+        alias = "BarChartUsage",
+        alias = "BarChartSome",
+        alias = "BarChartCpu"
+    )]
     BarLeft { color: Color, aspect_ratio: f32 },
-    #[serde(alias = "BarChartSwap")]
+    #[serde(
+        alias = "BarChartSwap",
+        alias = "BarChartVram",
+        alias = "BarChartFull",
+        alias = "BarChartMemory"
+    )]
     BarRight { color: Color, aspect_ratio: f32 },
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views of the RAM split by usage, stacked in the order of [`MemoryColors`]
+pub enum MemoryView {
+    #[serde(rename = "StackedRunChart")]
+    Run {
+        #[serde(default)]
+        colors: MemoryColors,
+        aspect_ratio: f32,
+    },
+    #[serde(rename = "SegmentedBarChart")]
+    Bar {
+        #[serde(default)]
+        colors: MemoryColors,
+        aspect_ratio: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct MemoryColors {
+    /// memory that can't be reclaimed, excluding `shmem` and `dirty`
+    pub used: Color,
+    /// shared memory and tmpfs
+    pub shmem: Color,
+    /// page cache waiting to be or being written to disk
+    pub dirty: Color,
+    pub buffers: Color,
+    /// page cache that can be reclaimed
+    pub cache: Color,
+}
+
+impl Default for MemoryColors {
+    fn default() -> Self {
+        Self {
+            used: Color::accent_green,
+            shmem: Color::accent_purple,
+            dirty: Color::accent_orange,
+            buffers: Color::accent_yellow,
+            cache: Color::accent_blue,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for components that monitor a single value
+pub enum SimpleView {
+    #[serde(rename = "RunChart")]
+    Run { color: Color, aspect_ratio: f32 },
+    #[serde(rename = "BarChart")]
+    Bar { color: Color, aspect_ratio: f32 },
+    /// The current value as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the space used in each filesystem
+pub enum FsView {
+    /// One bar per mount point
+    #[serde(rename = "BarChart")]
+    Bar {
+        color: Color,
+        #[serde(default = "default_color_warning")]
+        color_warning: Color,
+        #[serde(default = "default_color_critical")]
+        color_critical: Color,
+        spacing: f32,
+        aspect_ratio: f32,
+    },
+    /// The used and total space of each mount point as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+pub enum LoadAverage {
+    #[default]
+    One,
+    Five,
+    Fifteen,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the load averages
+pub enum LoadView {
+    /// The 1 minute load average in front of the 15 minutes one
+    #[serde(rename = "RunChart")]
+    Run {
+        color_back: Color,
+        color_front: Color,
+        aspect_ratio: f32,
+    },
+    /// A single load average
+    #[serde(rename = "RunChartAverage")]
+    RunAverage {
+        #[serde(default)]
+        average: LoadAverage,
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// The 1, 5 and 15 minutes load averages as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Resources reported in `/proc/pressure`
+pub enum PsiResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PsiResource {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            PsiResource::Cpu => "cpu",
+            PsiResource::Memory => "memory",
+            PsiResource::Io => "io",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// How the stalled time is sampled
+pub enum PsiValue {
+    /// The kernel's average over the last 10 seconds
+    #[default]
+    Avg10,
+    /// The increase of the total stalled time between samples
+    Total,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// Socket counts read from `/proc/net`
+pub enum SocketCount {
+    /// TCP connections in the `ESTABLISHED` state
+    #[default]
+    Established,
+    /// TCP sockets in the `LISTEN` state
+    Listen,
+    /// TCP connections in the `TIME_WAIT` state
+    TimeWait,
+    /// Sockets of every protocol
+    Total,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the TCP connections and sockets
+pub enum TcpView {
+    /// A single socket count
+    #[serde(rename = "RunChart")]
+    Run {
+        #[serde(default)]
+        count: SocketCount,
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// The established, listening, `TIME_WAIT` and total counts as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// Values reported in `/proc/net/wireless`
+pub enum WifiValue {
+    /// The link quality computed by the driver
+    #[default]
+    Quality,
+    /// The signal level, where -50 dBm or more is shown as 100%
+    Level,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// How the round-trip time is measured
+pub enum LatencyProbe {
+    /// Time taken to open a TCP connection
+    #[default]
+    Tcp,
+    /// ICMP echo requests sent by the `ping` command
+    Icmp,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the round-trip time
+pub enum LatencyView {
+    /// The round-trip time, with gaps for the lost probes
+    #[serde(rename = "RunChart")]
+    Run { color: Color, aspect_ratio: f32 },
+    /// The percentage of lost probes in the sampling window
+    #[serde(rename = "BarChartLoss")]
+    BarLoss { color: Color, aspect_ratio: f32 },
+    /// The latest round-trip time and the percentage of lost probes as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// Rates computed from the counters in `/proc/stat`
+pub enum StatRate {
+    /// Context switches per second
+    #[default]
+    ContextSwitches,
+    /// Interrupts serviced per second
+    Interrupts,
+    /// Processes and threads created per second
+    Forks,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the kernel activity rates
+pub enum RateView {
+    /// A single rate
+    #[serde(rename = "RunChart")]
+    Run {
+        #[serde(default)]
+        rate: StatRate,
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// The context switch, interrupt and fork rates as text
+    Text,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Default)]
+/// Counts of the processes listed in `/proc`
+pub enum ProcessCount {
+    #[default]
+    Processes,
+    /// Threads of every process
+    Threads,
+    /// Processes that exited but weren't reaped by their parent
+    Zombies,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Views for the number of processes
+pub enum ProcessView {
+    /// A single count
+    #[serde(rename = "RunChart")]
+    Run {
+        #[serde(default)]
+        count: ProcessCount,
+        color: Color,
+        aspect_ratio: f32,
+    },
+    /// The process, thread and zombie counts as text
+    Text,
+}
+
+fn default_net_exclude() -> Box<[String]> {
+    ["lo".to_owned()].into()
+}
+
+fn default_max_temp() -> f32 {
+    100.0
+}
+
+fn default_latency_timeout() -> u64 {
+    1000
+}
+
+fn default_command_timeout() -> u64 {
+    1000
+}
+
+fn default_fs_warning() -> f32 {
+    80.0
+}
+
+fn default_fs_critical() -> f32 {
+    95.0
+}
+
+fn default_color_warning() -> Color {
+    Color::accent_yellow
+}
+
+fn default_color_critical() -> Color {
+    Color::accent_red
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -248,10 +803,20 @@ impl Default for Config {
                 ComponentConfig::default_mem(),
                 ComponentConfig::default_disk(),
                 ComponentConfig::default_net(),
-                // ComponentConfig::default_gpu(),
+                ComponentConfig::default_gpu(),
             ]
             .into(),
             sampling: SamplingConfig::default(),
+            popup: PopupConfig::default(),
+        }
+    }
+}
+
+impl Default for PopupConfig {
+    fn default() -> Self {
+        Self {
+            processes: 5,
+            interrupts: 0,
         }
     }
 }
@@ -289,6 +854,54 @@ impl Default for SamplingConfig {
                 update_interval: 2000,
                 sampling_window: 30,
             },
+            temp: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            load: Sampling {
+                update_interval: 5000,
+                sampling_window: 60,
+            },
+            psi: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            battery: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            fan: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            cgroup: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            tcp: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            wifi: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            latency: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            rate: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            processes: Sampling {
+                update_interval: 2000,
+                sampling_window: 30,
+            },
+            command: Sampling {
+                update_interval: 5000,
+                sampling_window: 30,
+            },
         }
     }
 }
@@ -315,8 +928,8 @@ impl ComponentConfig {
     fn default_mem() -> Self {
         let color_back = Color::accent_green;
         let color_front = Color::accent_purple;
-        ComponentConfig::Mem(
-            [
+        ComponentConfig::Mem {
+            views: [
                 PercentView::Run {
                     color_back,
                     color_front,
@@ -330,49 +943,63 @@ impl ComponentConfig {
                 },
             ]
             .into(),
-        )
+            breakdown: [].into(),
+            swap_views: [].into(),
+            fault_views: [].into(),
+        }
     }
 
     fn default_net() -> Self {
-        ComponentConfig::Net(
-            [IoView::Run {
+        ComponentConfig::Net {
+            views: [IoView::Run {
                 color_front: Color::accent_yellow,
                 color_back: Color::accent_red,
                 aspect_ratio: 1.5,
             }]
             .into(),
-        )
+            include: [].into(),
+            exclude: default_net_exclude(),
+        }
     }
 
     fn default_disk() -> Self {
-        ComponentConfig::Disk(
-            [IoView::Run {
+        ComponentConfig::Disk {
+            views: [IoView::Run {
                 color_front: Color::accent_orange,
                 color_back: Color::accent_pink,
                 aspect_ratio: 1.5,
             }]
             .into(),
-        )
+            busy_views: [].into(),
+            iops_views: [].into(),
+            latency_views: [].into(),
+            devices: [].into(),
+            mounts: [].into(),
+        }
     }
 
-    // fn default_gpu() -> Self {
-    //     let color_back = Color::accent_warm_grey;
-    //     let color_front = Color::accent_indigo;
-    //     ComponentConfig::Mem(
-    //         [
-    //             PercentView::Run {
-    //                 color_back,
-    //                 color_front,
-    //                 aspect_ratio: 1.5,
-    //             },
-    //             PercentView::Bar {
-    //                 color_back,
-    //                 color_front,
-    //                 bar_aspect_ratio: 0.5,
-    //                 spacing: 2.5,
-    //             },
-    //         ]
-    //         .into(),
-    //     )
-    // }
+    fn default_gpu() -> Self {
+        let color_back = Color::accent_warm_grey;
+        let color_front = Color::accent_indigo;
+        ComponentConfig::Gpu {
+            card: None,
+            views: [
+                PercentView::Run {
+                    color_back,
+                    color_front,
+                    aspect_ratio: 1.5,
+                },
+                PercentView::Bar {
+                    color_left: color_back,
+                    color_right: color_front,
+                    aspect_ratio: 0.5,
+                    spacing: 2.5,
+                },
+            ]
+            .into(),
+            temp_views: [].into(),
+            max_temp: default_max_temp(),
+            power_views: [].into(),
+        }
+    }
 }
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use glob::Pattern;
+
+/// Whether `name` matches one of the `include` glob patterns (or `include` is empty) and none of the `exclude` ones
+pub fn matches(name: &str, include: &[String], exclude: &[String]) -> bool {
+    let matches_any = |patterns: &[String]| {
+        patterns
+            .iter()
+            .any(|pattern| Pattern::new(pattern).is_ok_and(|pattern| pattern.matches(name)))
+    };
+
+    (include.is_empty() || matches_any(include)) && !matches_any(exclude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<String> {
+        patterns.iter().map(|&pattern| pattern.to_owned()).collect()
+    }
+
+    #[test]
+    fn empty_include_matches_everything() {
+        assert!(matches("wlan0", &[], &[]));
+        assert!(matches("lo", &[], &[]));
+    }
+
+    #[test]
+    fn include_patterns_are_globs() {
+        let include = patterns(&["en*", "wl?0"]);
+        assert!(matches("enp3s0", &include, &[]));
+        assert!(matches("wlp0", &include, &[]));
+        assert!(!matches("wlan0", &include, &[]));
+        assert!(!matches("docker0", &include, &[]));
+    }
+
+    #[test]
+    fn exclude_wins_over_include() {
+        let include = patterns(&["*"]);
+        let exclude = patterns(&["veth*"]);
+        assert!(matches("eth0", &include, &exclude));
+        assert!(!matches("veth1a2b", &include, &exclude));
+    }
+
+    #[test]
+    fn default_net_exclude_skips_loopback() {
+        let exclude = patterns(&["lo"]);
+        assert!(!matches("lo", &[], &exclude));
+        // only the whole name is matched
+        assert!(matches("lo0x", &[], &exclude));
+        assert!(matches("enp3s0", &[], &exclude));
+    }
+
+    #[test]
+    fn invalid_patterns_match_nothing() {
+        let invalid = patterns(&["[en"]);
+        assert!(!matches("[en", &invalid, &[]));
+        assert!(!matches("enp3s0", &invalid, &[]));
+        // so they don't exclude anything either
+        assert!(matches("enp3s0", &[], &invalid));
+    }
+}
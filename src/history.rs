@@ -34,6 +34,12 @@ impl<T: Default + Copy> History<T> {
         self.insertion_index = (self.insertion_index + 1) % self.capacity;
     }
 
+    /// The most recently pushed value
+    #[inline]
+    pub fn latest(&self) -> T {
+        self.data[(self.insertion_index + self.capacity - 1) % self.capacity]
+    }
+
     pub fn iter(&self) -> Chain<Iter<T>, Iter<T>> {
         let (a, b) = self.data.split_at(self.insertion_index);
         b.iter().chain(a.iter())
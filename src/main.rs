@@ -7,8 +7,34 @@ mod components {
 }
 mod color;
 mod config;
+mod filter;
 mod history;
 mod localization;
+mod sources {
+    pub mod battery;
+    pub mod block;
+    pub mod cgroup;
+    pub mod command;
+    pub mod cpufreq;
+    pub mod diskstats;
+    #[cfg(test)]
+    pub mod fixture;
+    pub mod gpu;
+    pub mod hwmon;
+    pub mod interrupts;
+    pub mod latency;
+    pub mod meminfo;
+    #[cfg(feature = "nvidia")]
+    pub mod nvidia;
+    pub mod pressure;
+    pub mod rapl;
+    pub mod sockets;
+    pub mod stat;
+    pub mod sysfs;
+    pub mod tasks;
+    pub mod vmstat;
+    pub mod wireless;
+}
 
 use applet::{Flags, SystemMonitorApplet, ID};
 use config::{Config, CONFIG_VERSION};
@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use super::sysfs::{read_trimmed, read_value};
+
+pub const POWER_SUPPLY_ROOT: &str = "/sys/class/power_supply";
+
+/// Reader for the batteries exposed by the kernel under `/sys/class/power_supply`
+#[derive(Clone, Debug)]
+pub struct PowerSupply {
+    root: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Battery {
+    /// name of the power supply (e.g. `BAT0`)
+    pub name: String,
+    /// percentage of the full capacity
+    pub charge: f32,
+    pub charging: bool,
+    /// charge or discharge rate, in watts
+    pub power: f32,
+    /// energy stored, in watt-hours
+    pub energy: Option<f32>,
+    /// energy stored when full, in watt-hours
+    pub energy_full: Option<f32>,
+}
+
+impl Battery {
+    /// Time until the battery is full while charging, or empty while discharging
+    pub fn time_remaining(&self) -> Option<Duration> {
+        if self.power <= 0.0 {
+            return None;
+        }
+        let energy = if self.charging {
+            self.energy_full? - self.energy?
+        } else {
+            self.energy?
+        };
+        Some(Duration::from_secs_f32(
+            energy.max(0.0) / self.power * 3600.0,
+        ))
+    }
+}
+
+impl Default for PowerSupply {
+    fn default() -> Self {
+        Self::new(POWER_SUPPLY_ROOT)
+    }
+}
+
+impl PowerSupply {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The batteries of the system (`BAT*`), sorted by name
+    pub fn batteries(&self) -> Vec<Battery> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut batteries: Vec<_> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with("BAT") {
+                    return None;
+                }
+                let path = entry.path();
+                // values are reported in µV, µA, µW, µAh and µWh
+                let micro = |file: &str| read_value::<f32>(&path.join(file)).map(|v| v / 1e6);
+                let voltage = micro("voltage_now");
+
+                let energy = micro("energy_now").or_else(|| Some(micro("charge_now")? * voltage?));
+                let energy_full =
+                    micro("energy_full").or_else(|| Some(micro("charge_full")? * voltage?));
+                let charge = read_value(&path.join("capacity"))
+                    .or_else(|| Some(energy? / energy_full? * 100.0))?;
+                let power = micro("power_now")
+                    .or_else(|| Some(micro("current_now")? * voltage?))
+                    .unwrap_or_default()
+                    .abs();
+
+                Some(Battery {
+                    name,
+                    charge,
+                    charging: read_trimmed(&path.join("status")).as_deref() == Some("Charging"),
+                    power,
+                    energy,
+                    energy_full,
+                })
+            })
+            .collect();
+
+        batteries.sort_by(|a, b| a.name.cmp(&b.name));
+        batteries
+    }
+}
+
+/// Finds the battery named `name`, or the first battery if no name is given
+pub fn find<'a>(batteries: &'a [Battery], name: Option<&str>) -> Option<&'a Battery> {
+    match name {
+        Some(name) => batteries.iter().find(|battery| battery.name == name),
+        None => batteries.first(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    fn fixture() -> Fixture {
+        Fixture::new(
+            "power-supply",
+            &[
+                ("AC/online", "1\n"),
+                // energy_* layout, discharging
+                ("BAT0/status", "Discharging\n"),
+                ("BAT0/capacity", "80\n"),
+                ("BAT0/energy_now", "40000000\n"),
+                ("BAT0/energy_full", "50000000\n"),
+                ("BAT0/power_now", "10000000\n"),
+                // charge_* layout without capacity, charging
+                ("BAT1/status", "Charging\n"),
+                ("BAT1/voltage_now", "12000000\n"),
+                ("BAT1/charge_now", "2500000\n"),
+                ("BAT1/charge_full", "5000000\n"),
+                ("BAT1/current_now", "2500000\n"),
+                // charge_* layout reporting the discharge as a negative current
+                ("BAT2/status", "Discharging\n"),
+                ("BAT2/capacity", "25\n"),
+                ("BAT2/voltage_now", "12000000\n"),
+                ("BAT2/charge_now", "1000000\n"),
+                ("BAT2/charge_full", "4000000\n"),
+                ("BAT2/current_now", "-1000000\n"),
+                // no rate
+                ("BAT3/status", "Not charging\n"),
+                ("BAT3/capacity", "100\n"),
+            ],
+        )
+    }
+
+    #[test]
+    fn reads_energy_layout() {
+        let root = fixture();
+        let batteries = PowerSupply::new(&*root).batteries();
+        assert_eq!(
+            batteries[0],
+            Battery {
+                name: "BAT0".to_owned(),
+                charge: 80.0,
+                charging: false,
+                power: 10.0,
+                energy: Some(40.0),
+                energy_full: Some(50.0),
+            }
+        );
+    }
+
+    #[test]
+    fn computes_energy_and_power_of_charge_layout() {
+        let root = fixture();
+        let batteries = PowerSupply::new(&*root).batteries();
+        assert_eq!(
+            batteries[1],
+            Battery {
+                name: "BAT1".to_owned(),
+                charge: 50.0,
+                charging: true,
+                power: 30.0,
+                energy: Some(30.0),
+                energy_full: Some(60.0),
+            }
+        );
+        assert_eq!(
+            batteries[2],
+            Battery {
+                name: "BAT2".to_owned(),
+                charge: 25.0,
+                charging: false,
+                power: 12.0,
+                energy: Some(12.0),
+                energy_full: Some(48.0),
+            }
+        );
+    }
+
+    #[test]
+    fn lists_only_batteries() {
+        let root = fixture();
+        let batteries = PowerSupply::new(&*root).batteries();
+        let names: Vec<_> = batteries
+            .iter()
+            .map(|battery| battery.name.as_str())
+            .collect();
+        assert_eq!(names, ["BAT0", "BAT1", "BAT2", "BAT3"]);
+        assert_eq!(find(&batteries, Some("BAT2")), Some(&batteries[2]));
+        assert_eq!(find(&batteries, None), Some(&batteries[0]));
+        assert_eq!(find(&batteries, Some("BAT9")), None);
+    }
+
+    #[test]
+    fn estimates_time_remaining() {
+        let root = fixture();
+        let batteries = PowerSupply::new(&*root).batteries();
+        // 40 Wh left at 10 W
+        assert_eq!(
+            batteries[0].time_remaining(),
+            Some(Duration::from_secs(4 * 3600))
+        );
+        // 30 Wh to full at 30 W
+        assert_eq!(
+            batteries[1].time_remaining(),
+            Some(Duration::from_secs(3600))
+        );
+        // 12 Wh left at 12 W
+        assert_eq!(
+            batteries[2].time_remaining(),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(batteries[3].time_remaining(), None);
+    }
+}
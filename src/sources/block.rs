@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const BLOCK_ROOT: &str = "/sys/class/block";
+
+/// Resolves the block devices behind the entries of `sysinfo::Disks`
+#[derive(Clone, Debug)]
+pub struct BlockDevices {
+    root: PathBuf,
+}
+
+impl Default for BlockDevices {
+    fn default() -> Self {
+        Self::new(BLOCK_ROOT)
+    }
+}
+
+impl BlockDevices {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Kernel name of a device node, following symlinks (e.g. `/dev/mapper/root` is `dm-0`)
+    pub fn kernel_name(device: &OsStr) -> String {
+        let path = Path::new(device);
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        path.strip_prefix("/dev")
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The disk holding the partition `name`, or `name` itself if it isn't a partition
+    ///
+    /// Device-mapper devices (e.g. LUKS, LVM) stacked on a single device are resolved to the disk below them.
+    pub fn disk_of(&self, name: &str) -> String {
+        let path = self.root.join(name);
+        if !path.join("partition").exists() {
+            let slaves: Vec<_> = fs::read_dir(path.join("slaves"))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .collect();
+            return match slaves.as_slice() {
+                [slave] => self.disk_of(&slave.file_name().to_string_lossy()),
+                _ => name.to_owned(),
+            };
+        }
+
+        // partitions are listed as subdirectories of their disk
+        fs::canonicalize(&path)
+            .ok()
+            .and_then(|path| Some(path.parent()?.file_name()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| name.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+    use std::os::unix::fs::symlink;
+
+    /// Block devices as laid out by sysfs: the entries of `block` link to their device directories
+    fn devices() -> Fixture {
+        let root = Fixture::new(
+            "block",
+            &[
+                ("devices/sda/sda1/partition", "1\n"),
+                ("devices/sda/sda2/partition", "2\n"),
+                // LUKS on sda2, with LVM on top of it
+                ("devices/dm-0/slaves/sda2", ""),
+                ("devices/dm-1/slaves/dm-0", ""),
+                // RAID over two disks
+                ("devices/md0/slaves/sdb", ""),
+                ("devices/md0/slaves/sdc", ""),
+                ("devices/sdb/size", "0\n"),
+                ("devices/sdc/size", "0\n"),
+            ],
+        );
+        fs::create_dir(root.join("block")).unwrap();
+        for (name, device) in [
+            ("sda", "sda"),
+            ("sda1", "sda/sda1"),
+            ("sda2", "sda/sda2"),
+            ("dm-0", "dm-0"),
+            ("dm-1", "dm-1"),
+            ("md0", "md0"),
+            ("sdb", "sdb"),
+            ("sdc", "sdc"),
+        ] {
+            symlink(
+                Path::new("../devices").join(device),
+                root.join("block").join(name),
+            )
+            .unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn resolves_partitions_to_their_disk() {
+        let root = devices();
+        let devices = BlockDevices::new(root.join("block"));
+        assert_eq!(devices.disk_of("sda1"), "sda");
+        assert_eq!(devices.disk_of("sda2"), "sda");
+        assert_eq!(devices.disk_of("sda"), "sda");
+    }
+
+    #[test]
+    fn resolves_device_mapper_stacks_to_their_disk() {
+        let root = devices();
+        let devices = BlockDevices::new(root.join("block"));
+        assert_eq!(devices.disk_of("dm-0"), "sda");
+        assert_eq!(devices.disk_of("dm-1"), "sda");
+    }
+
+    #[test]
+    fn keeps_devices_over_several_disks() {
+        let root = devices();
+        let devices = BlockDevices::new(root.join("block"));
+        assert_eq!(devices.disk_of("md0"), "md0");
+        assert_eq!(devices.disk_of("nvme0n1"), "nvme0n1");
+    }
+
+    #[test]
+    fn names_devices_relative_to_dev() {
+        let device = OsStr::new("/dev/mapper/system-monitor-missing");
+        assert_eq!(
+            BlockDevices::kernel_name(device),
+            "mapper/system-monitor-missing"
+        );
+        assert_eq!(BlockDevices::kernel_name(OsStr::new("tmpfs")), "tmpfs");
+    }
+}
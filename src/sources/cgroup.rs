@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+use super::sysfs::{read_trimmed, read_value};
+
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// Reader for the cgroup v2 hierarchy mounted at `/sys/fs/cgroup`
+#[derive(Clone, Debug)]
+pub struct Cgroups {
+    root: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CgroupSample {
+    /// cpu time used by the cgroup since its creation, in microseconds
+    pub cpu_usage: u64,
+    /// memory used by the cgroup, in bytes
+    pub memory: u64,
+    /// memory limit of the cgroup, in bytes, if it has one
+    pub memory_max: Option<u64>,
+}
+
+impl Default for Cgroups {
+    fn default() -> Self {
+        Self::new(CGROUP_ROOT)
+    }
+}
+
+impl Cgroups {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Reads the cgroup at `path`, relative to the root (e.g. `user.slice`, `system.slice/docker.service`)
+    pub fn read(&self, path: &str) -> Option<CgroupSample> {
+        let cgroup = self.root.join(path.trim_start_matches('/'));
+        let cpu_usage = fs::read_to_string(cgroup.join("cpu.stat"))
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(CgroupSample {
+            cpu_usage,
+            memory: read_value(&cgroup.join("memory.current")).unwrap_or_default(),
+            // unlimited cgroups report `max`
+            memory_max: read_trimmed(&cgroup.join("memory.max")).and_then(|max| max.parse().ok()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    fn fixture() -> Fixture {
+        Fixture::new(
+            "cgroup",
+            &[
+                (
+                    "user.slice/cpu.stat",
+                    "usage_usec 8012345\nuser_usec 6000000\nsystem_usec 2012345\n",
+                ),
+                ("user.slice/memory.current", "1073741824\n"),
+                ("user.slice/memory.max", "max\n"),
+                (
+                    "system.slice/docker.service/cpu.stat",
+                    "usage_usec 42\nuser_usec 40\nsystem_usec 2\n",
+                ),
+                ("system.slice/docker.service/memory.current", "4096\n"),
+                ("system.slice/docker.service/memory.max", "536870912\n"),
+            ],
+        )
+    }
+
+    #[test]
+    fn reads_cpu_usage_and_memory() {
+        let root = fixture();
+        assert_eq!(
+            Cgroups::new(&*root).read("user.slice"),
+            Some(CgroupSample {
+                cpu_usage: 8_012_345,
+                memory: 1 << 30,
+                // unlimited
+                memory_max: None,
+            })
+        );
+    }
+
+    #[test]
+    fn reads_nested_cgroups_and_their_limit() {
+        let root = fixture();
+        let sample = Cgroups::new(&*root)
+            .read("/system.slice/docker.service")
+            .unwrap();
+        assert_eq!(sample.cpu_usage, 42);
+        assert_eq!(sample.memory, 4096);
+        assert_eq!(sample.memory_max, Some(512 << 20));
+    }
+
+    #[test]
+    fn missing_cgroup_has_no_sample() {
+        let root = fixture();
+        assert_eq!(Cgroups::new(&*root).read("init.scope"), None);
+    }
+}
@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::{self, Read},
+    process::{Child, Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Time between the checks of whether the command exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Bytes of the output that are kept to be parsed
+const MAX_OUTPUT: u64 = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CommandSample {
+    pub value: f32,
+    /// maximum reported with the value, as `<value>/<max>`
+    pub max: Option<f32>,
+}
+
+/// Runs `command` (the program followed by its arguments) and parses its output
+///
+/// The command is killed if it doesn't exit before `timeout`, and fails if it exits with an error.
+pub fn run(command: &[String], timeout: Duration) -> Option<CommandSample> {
+    let (program, args) = command.split_first()?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let Some(mut stdout) = child.stdout.take() else {
+        stop(&mut child);
+        return None;
+    };
+
+    // the output is read while the command runs, so that it isn't blocked by a full pipe
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        // only the first line is parsed, so the rest of a long output is discarded
+        let read = (&mut stdout)
+            .take(MAX_OUTPUT)
+            .read_to_end(&mut output)
+            .and_then(|_| io::copy(&mut stdout, &mut io::sink()));
+        _ = sender.send(read.map(|_| output));
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            // timed out, or the command can't be waited for
+            _ => {
+                stop(&mut child);
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+
+    // processes started by the command can keep the pipe open after it exits, so the output is waited for until the
+    // deadline at most
+    let output = receiver
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()?
+        .ok()?;
+    parse(&String::from_utf8_lossy(&output))
+}
+
+/// Kills `child` and reaps it, so that it doesn't linger as a zombie
+fn stop(child: &mut Child) {
+    _ = child.kill();
+    _ = child.wait();
+}
+
+/// Parses the first line of the output, either a number or a `<value>/<max>` pair
+fn parse(output: &str) -> Option<CommandSample> {
+    let line = output
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    // `f32` also parses `nan` and `inf`, which can't be drawn
+    let number = |text: &str| {
+        text.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|value| value.is_finite())
+    };
+    match line.split_once('/') {
+        Some((value, max)) => Some(CommandSample {
+            value: number(value)?,
+            max: Some(number(max)?),
+        }),
+        None => Some(CommandSample {
+            value: number(line)?,
+            max: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn sh(script: &str) -> Vec<String> {
+        ["sh", "-c", script].map(ToOwned::to_owned).into()
+    }
+
+    #[test]
+    fn parses_values_and_maximums() {
+        assert_eq!(
+            parse("\n 42.5 \n7\n"),
+            Some(CommandSample {
+                value: 42.5,
+                max: None,
+            })
+        );
+        assert_eq!(
+            parse("3 / 4\n"),
+            Some(CommandSample {
+                value: 3.0,
+                max: Some(4.0),
+            })
+        );
+        assert_eq!(parse("n/a\n"), None);
+    }
+
+    #[test]
+    fn rejects_non_finite_values() {
+        assert_eq!(parse("nan\n"), None);
+        assert_eq!(parse("inf\n"), None);
+        assert_eq!(parse("-inf\n"), None);
+        assert_eq!(parse("1/inf\n"), None);
+        assert_eq!(parse("NaN/100\n"), None);
+    }
+
+    #[test]
+    fn reads_outputs_larger_than_the_pipe() {
+        let sample = run(&sh("echo 12; head -c 1000000 /dev/zero"), TIMEOUT);
+        assert_eq!(
+            sample,
+            Some(CommandSample {
+                value: 12.0,
+                max: None,
+            })
+        );
+    }
+
+    #[test]
+    fn fails_with_the_command() {
+        assert_eq!(run(&sh("echo 1; exit 1"), TIMEOUT), None);
+        assert_eq!(run(&["/nonexistent".to_owned()], TIMEOUT), None);
+        assert_eq!(run(&[], TIMEOUT), None);
+    }
+
+    #[test]
+    fn kills_the_command_after_the_timeout() {
+        let start = Instant::now();
+        assert_eq!(run(&sh("sleep 10"), Duration::from_millis(100)), None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::path::PathBuf;
+
+use super::sysfs::read_value;
+
+pub const CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// Reader for the frequency scaling limits exposed by cpufreq under `/sys/devices/system/cpu`
+#[derive(Clone, Debug)]
+pub struct CpuFreq {
+    root: PathBuf,
+}
+
+impl Default for CpuFreq {
+    fn default() -> Self {
+        Self::new(CPU_ROOT)
+    }
+}
+
+impl CpuFreq {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Minimum and maximum frequencies of `cpu` (e.g. `cpu0`), in MHz
+    pub fn limits(&self, cpu: &str) -> Option<(u64, u64)> {
+        let cpufreq = self.root.join(cpu).join("cpufreq");
+        let min: u64 = read_value(&cpufreq.join("cpuinfo_min_freq"))?;
+        let max: u64 = read_value(&cpufreq.join("cpuinfo_max_freq"))?;
+        Some((min / 1000, max / 1000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    #[test]
+    fn reads_limits_in_mhz() {
+        let root = Fixture::new(
+            "cpufreq",
+            &[
+                ("cpu0/cpufreq/cpuinfo_min_freq", "400000\n"),
+                ("cpu0/cpufreq/cpuinfo_max_freq", "4672000\n"),
+                ("cpu1/cpufreq/cpuinfo_max_freq", "4672000\n"),
+            ],
+        );
+        let cpufreq = CpuFreq::new(&*root);
+        assert_eq!(cpufreq.limits("cpu0"), Some((400, 4672)));
+        // both limits are needed
+        assert_eq!(cpufreq.limits("cpu1"), None);
+        assert_eq!(cpufreq.limits("cpu2"), None);
+    }
+}
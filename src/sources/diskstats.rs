@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const DISKSTATS_PATH: &str = "/proc/diskstats";
+
+/// Reader for the I/O statistics of the block devices in `/proc/diskstats`
+#[derive(Clone, Debug)]
+pub struct DiskStats {
+    path: PathBuf,
+}
+
+/// I/O counters of a block device since boot
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiskStat {
+    /// kernel name of the device (e.g. `nvme0n1`, `sda1`, `dm-0`)
+    pub name: String,
+    /// reads completed
+    pub reads: u64,
+    /// time spent by the reads, in milliseconds
+    pub read_ticks: u64,
+    /// writes completed
+    pub writes: u64,
+    /// time spent by the writes, in milliseconds
+    pub write_ticks: u64,
+    /// time in which the device had requests in flight, in milliseconds
+    pub io_ticks: u64,
+}
+
+impl DiskStat {
+    /// Counters increased since `previous`
+    #[must_use]
+    pub fn since(&self, previous: &DiskStat) -> DiskStat {
+        DiskStat {
+            name: self.name.clone(),
+            reads: self.reads.saturating_sub(previous.reads),
+            read_ticks: self.read_ticks.saturating_sub(previous.read_ticks),
+            writes: self.writes.saturating_sub(previous.writes),
+            write_ticks: self.write_ticks.saturating_sub(previous.write_ticks),
+            io_ticks: self.io_ticks.saturating_sub(previous.io_ticks),
+        }
+    }
+}
+
+impl Default for DiskStats {
+    fn default() -> Self {
+        Self::new(DISKSTATS_PATH)
+    }
+}
+
+impl DiskStats {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The counters of every block device, in the order reported by the kernel
+    pub fn read(&self) -> Vec<DiskStat> {
+        fs::read_to_string(&self.path)
+            .map(|content| parse(&content))
+            .unwrap_or_default()
+    }
+}
+
+fn parse(content: &str) -> Vec<DiskStat> {
+    content
+        .lines()
+        .filter_map(|line| {
+            // major, minor and name, followed by the counters
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let counter = |index: usize| fields.get(index)?.parse::<u64>().ok();
+            Some(DiskStat {
+                name: (*fields.get(2)?).to_owned(),
+                reads: counter(3)?,
+                read_ticks: counter(6)?,
+                writes: counter(7)?,
+                write_ticks: counter(10)?,
+                io_ticks: counter(12)?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const DISKSTATS: &str = "\
+ 259       0 nvme0n1 273491 94178 20934770 41233 529014 332871 34728160 381021 0 299420 443186 0 0 0 0 41874 20931
+ 259       1 nvme0n1p1 326 1056 14896 72 2 0 2 0 0 88 72 0 0 0 0 0 0
+   7       0 loop0
+";
+
+    #[test]
+    fn parses_the_counters_of_each_device() {
+        let stats = parse(DISKSTATS);
+        assert_eq!(
+            stats,
+            [
+                DiskStat {
+                    name: "nvme0n1".to_owned(),
+                    reads: 273_491,
+                    read_ticks: 41233,
+                    writes: 529_014,
+                    write_ticks: 381_021,
+                    io_ticks: 299_420,
+                },
+                DiskStat {
+                    name: "nvme0n1p1".to_owned(),
+                    reads: 326,
+                    read_ticks: 72,
+                    writes: 2,
+                    write_ticks: 0,
+                    io_ticks: 88,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counters_since_never_go_below_0() {
+        let stats = parse(DISKSTATS);
+        let delta = stats[0].since(&DiskStat {
+            reads: 273_000,
+            io_ticks: 300_000,
+            ..stats[0].clone()
+        });
+        assert_eq!((delta.reads, delta.writes, delta.io_ticks), (491, 0, 0));
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("diskstats", &[("diskstats", DISKSTATS)]);
+        assert_eq!(DiskStats::new(root.join("diskstats")).read().len(), 2);
+        assert!(DiskStats::new(root.join("missing")).read().is_empty());
+    }
+}
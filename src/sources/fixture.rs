@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Number of fixtures created, so that the tests running in parallel threads each get their own directory
+static FIXTURES: AtomicUsize = AtomicUsize::new(0);
+
+/// Directory of fake kernel files for the tests of the readers, removed when dropped
+pub struct Fixture {
+    root: PathBuf,
+}
+
+impl Fixture {
+    /// Writes `files`, as pairs of a path relative to the root and its content, in a new directory named after `name`
+    pub fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let count = FIXTURES.fetch_add(1, Ordering::Relaxed);
+        let root = env::temp_dir().join(format!("system-monitor-{name}-{}-{count}", process::id()));
+        _ = fs::remove_dir_all(&root);
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        Self { root }
+    }
+}
+
+impl Deref for Fixture {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        _ = fs::remove_dir_all(&self.root);
+    }
+}
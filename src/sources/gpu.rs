@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use super::sysfs::read_value;
+
+pub const DRM_ROOT: &str = "/sys/class/drm";
+
+/// Source of GPU samples, implemented once per driver interface
+pub trait GpuBackend {
+    /// Samples every GPU handled by the backend
+    fn sample(&mut self) -> Vec<GpuSample>;
+}
+
+/// Every backend available on this machine
+pub fn backends() -> Vec<Box<dyn GpuBackend>> {
+    #[allow(unused_mut)]
+    let mut backends: Vec<Box<dyn GpuBackend>> = vec![Box::new(Drm::default())];
+
+    #[cfg(feature = "nvidia")]
+    match super::nvidia::Nvidia::init() {
+        Ok(nvidia) => backends.push(Box::new(nvidia)),
+        Err(err) => println!("NVML not available: {err}"),
+    }
+
+    backends
+}
+
+/// Reader for the GPU statistics exposed by the DRM drivers under `/sys/class/drm`
+///
+/// `amdgpu` reports the usage and VRAM of its cards, while `i915` and `xe` only report the time their
+/// GPUs spent idle, from which the usage is computed between samples.
+#[derive(Clone, Debug)]
+pub struct Drm {
+    root: PathBuf,
+    /// idle residency of the Intel cards, in milliseconds, and the time it was read
+    last_idle: HashMap<String, (Instant, u64)>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GpuSample {
+    /// name of the DRM card (e.g. `card0`)
+    pub card: String,
+    /// percentage of time the GPU was busy
+    pub usage: Option<f32>,
+    /// VRAM used, in bytes, not reported for integrated GPUs
+    pub vram_used: Option<u64>,
+    /// VRAM available, in bytes, not reported for integrated GPUs
+    pub vram_total: Option<u64>,
+    /// temperature, in degrees Celsius
+    pub temperature: Option<f32>,
+    /// power draw, in watts
+    pub power: Option<f32>,
+    /// maximum power draw allowed, in watts
+    pub power_limit: Option<f32>,
+}
+
+impl Default for Drm {
+    fn default() -> Self {
+        Self::new(DRM_ROOT)
+    }
+}
+
+impl Drm {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            last_idle: HashMap::new(),
+        }
+    }
+
+    /// Percentage of time the main GT of an Intel card was busy since the previous sample
+    #[allow(clippy::cast_precision_loss)]
+    fn intel_usage(&mut self, card: &str, path: &Path) -> Option<f32> {
+        let idle = idle_residency(path)?;
+        let now = Instant::now();
+        let usage = match self.last_idle.insert(card.to_owned(), (now, idle)) {
+            Some((time, last)) => {
+                let elapsed = now.duration_since(time).as_millis().max(1) as f32;
+                100.0 - (idle.saturating_sub(last) as f32 / elapsed * 100.0).min(100.0)
+            }
+            // nothing to compare with on the first sample
+            None => 0.0,
+        };
+        Some(usage)
+    }
+}
+
+impl GpuBackend for Drm {
+    /// Samples every card whose driver reports its usage or VRAM, sorted by card number
+    fn sample(&mut self) -> Vec<GpuSample> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut cards: Vec<(u32, GpuSample)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let card = entry.file_name().into_string().ok()?;
+                // connectors are listed as `card<n>-<connector>`
+                let number = card.strip_prefix("card")?.parse().ok()?;
+                let device = entry.path().join("device");
+
+                let usage = read_value(&device.join("gpu_busy_percent"))
+                    .or_else(|| self.intel_usage(&card, &entry.path()));
+                let vram_used = read_value(&device.join("mem_info_vram_used"));
+                let vram_total = read_value(&device.join("mem_info_vram_total"));
+                if usage.is_none() && vram_total.is_none() {
+                    return None;
+                }
+
+                let hwmon = first_hwmon(&device);
+                let hwmon_value = |file: &str, divisor: f32| {
+                    hwmon
+                        .as_ref()
+                        .and_then(|hwmon| read_value::<f32>(&hwmon.join(file)))
+                        .map(|value| value / divisor)
+                };
+
+                let sample = GpuSample {
+                    card,
+                    usage,
+                    vram_used,
+                    vram_total,
+                    temperature: hwmon_value("temp1_input", 1000.0),
+                    power: hwmon_value("power1_average", 1_000_000.0)
+                        .or_else(|| hwmon_value("power1_input", 1_000_000.0)),
+                    power_limit: hwmon_value("power1_cap", 1_000_000.0),
+                };
+                Some((number, sample))
+            })
+            .collect();
+
+        cards.sort_by_key(|(number, _)| *number);
+        cards.into_iter().map(|(_, sample)| sample).collect()
+    }
+}
+
+/// Time the main GT of an Intel card spent idle since boot, in milliseconds
+fn idle_residency(card: &Path) -> Option<u64> {
+    [
+        // xe
+        "device/tile0/gt0/gtidle/idle_residency_ms",
+        // i915
+        "gt/gt0/rc6_residency_ms",
+        // i915 on kernels without multi-GT support
+        "power/rc6_residency_ms",
+    ]
+    .into_iter()
+    .find_map(|file| read_value(&card.join(file)))
+}
+
+/// The hwmon directory of a DRM device, where its sensors are reported
+fn first_hwmon(device: &Path) -> Option<PathBuf> {
+    fs::read_dir(device.join("hwmon"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .next()
+}
+
+/// Finds the sample of `card`, or the first sample if no card is selected
+pub fn find<'a>(samples: &'a [GpuSample], card: Option<&str>) -> Option<&'a GpuSample> {
+    match card {
+        Some(card) => samples.iter().find(|sample| sample.card == card),
+        None => samples.first(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    #[test]
+    fn samples_amdgpu_and_intel_cards() {
+        let root = Fixture::new(
+            "drm",
+            &[
+                ("card0/device/gpu_busy_percent", "37\n"),
+                ("card0/device/mem_info_vram_used", "1073741824\n"),
+                ("card0/device/mem_info_vram_total", "8589934592\n"),
+                ("card0/device/hwmon/hwmon4/temp1_input", "51000\n"),
+                ("card0/device/hwmon/hwmon4/power1_average", "35000000\n"),
+                ("card0-DP-1/status", "connected\n"),
+                ("card1/gt/gt0/rc6_residency_ms", "1000\n"),
+                ("card2/device/tile0/gt0/gtidle/idle_residency_ms", "500\n"),
+                // e.g. a display-only device
+                ("card3/device/vendor", "0x1234\n"),
+            ],
+        );
+        let samples = Drm::new(&*root).sample();
+
+        assert_eq!(
+            samples[0],
+            GpuSample {
+                card: "card0".to_owned(),
+                usage: Some(37.0),
+                vram_used: Some(1 << 30),
+                vram_total: Some(8 << 30),
+                temperature: Some(51.0),
+                power: Some(35.0),
+                power_limit: None,
+            }
+        );
+        // i915 and xe don't report VRAM, and their usage is 0 until the next sample
+        for (sample, card) in samples[1..].iter().zip(["card1", "card2"]) {
+            assert_eq!(sample.card, card);
+            assert_eq!(sample.usage, Some(0.0));
+            assert_eq!(sample.vram_total, None);
+        }
+        assert_eq!(samples.len(), 3);
+    }
+
+    #[test]
+    fn intel_usage_is_the_time_not_idle() {
+        let root = Fixture::new("drm-intel", &[("card0/gt/gt0/rc6_residency_ms", "0\n")]);
+        let mut drm = Drm::new(&*root);
+        drm.sample();
+
+        // idle for longer than the time elapsed since the previous sample
+        fs::write(root.join("card0/gt/gt0/rc6_residency_ms"), "3600000\n").unwrap();
+        assert_eq!(drm.sample()[0].usage, Some(0.0));
+
+        // not idle at all
+        assert_eq!(drm.sample()[0].usage, Some(100.0));
+    }
+}
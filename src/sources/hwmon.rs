@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+use super::sysfs::{read_trimmed, read_value};
+
+pub const HWMON_ROOT: &str = "/sys/class/hwmon";
+
+/// Reader for the sensors exposed by the kernel under `/sys/class/hwmon`
+#[derive(Clone, Debug)]
+pub struct Hwmon {
+    root: PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sensor {
+    /// name of the hwmon chip (e.g. `k10temp`, `coretemp`, `amdgpu`)
+    pub chip: String,
+    /// value of `<kind><n>_label`, or `<kind><n>` if the chip doesn't label its sensors
+    pub label: String,
+    pub input: f32,
+    pub max: Option<f32>,
+}
+
+impl Sensor {
+    /// Matches either the bare label (e.g. `Tctl`) or the label prefixed by the chip name (e.g. `amdgpu edge`)
+    pub fn matches(&self, label: &str) -> bool {
+        self.label == label
+            || label
+                .strip_prefix(self.chip.as_str())
+                .and_then(|rest| rest.strip_prefix(' '))
+                .is_some_and(|rest| rest == self.label)
+    }
+}
+
+impl Default for Hwmon {
+    fn default() -> Self {
+        Self::new(HWMON_ROOT)
+    }
+}
+
+impl Hwmon {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Temperature sensors, in degrees Celsius
+    pub fn temperatures(&self) -> Vec<Sensor> {
+        self.sensors("temp", 1000.0)
+    }
+
+    /// Fan sensors, in RPM
+    pub fn fans(&self) -> Vec<Sensor> {
+        self.sensors("fan", 1.0)
+    }
+
+    fn sensors(&self, kind: &str, divisor: f32) -> Vec<Sensor> {
+        let Ok(chips) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut sensors = Vec::new();
+        for chip_dir in chips.flatten().map(|entry| entry.path()) {
+            let chip = read_trimmed(&chip_dir.join("name")).unwrap_or_default();
+            let Ok(entries) = fs::read_dir(&chip_dir) else {
+                continue;
+            };
+
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                let Some(index) = file_name
+                    .to_str()
+                    .and_then(|name| name.strip_prefix(kind))
+                    .and_then(|name| name.strip_suffix("_input"))
+                else {
+                    continue;
+                };
+                let Some(input) = read_value::<f32>(&entry.path()) else {
+                    continue;
+                };
+
+                let label = read_trimmed(&chip_dir.join(format!("{kind}{index}_label")))
+                    .unwrap_or_else(|| format!("{kind}{index}"));
+                let max = read_value::<f32>(&chip_dir.join(format!("{kind}{index}_max")));
+
+                sensors.push(Sensor {
+                    chip: chip.clone(),
+                    label,
+                    input: input / divisor,
+                    max: max.map(|max| max / divisor),
+                });
+            }
+        }
+
+        sensors.sort_by(|a, b| (&a.chip, &a.label).cmp(&(&b.chip, &b.label)));
+        sensors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    fn fixture() -> Fixture {
+        Fixture::new(
+            "hwmon",
+            &[
+                ("hwmon0/name", "k10temp\n"),
+                ("hwmon0/temp1_input", "45250\n"),
+                ("hwmon0/temp1_label", "Tctl\n"),
+                ("hwmon0/temp3_input", "40000\n"),
+                ("hwmon1/name", "amdgpu\n"),
+                ("hwmon1/temp1_input", "52000\n"),
+                ("hwmon1/temp1_label", "edge\n"),
+                ("hwmon1/temp1_max", "100000\n"),
+                ("hwmon1/fan1_input", "1200\n"),
+                ("hwmon1/fan1_max", "3300\n"),
+            ],
+        )
+    }
+
+    #[test]
+    fn reads_labels_and_millidegrees() {
+        let root = fixture();
+        let sensors = Hwmon::new(&*root).temperatures();
+        let values: Vec<_> = sensors
+            .iter()
+            .map(|sensor| (sensor.chip.as_str(), sensor.label.as_str(), sensor.input))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("amdgpu", "edge", 52.0),
+                ("k10temp", "Tctl", 45.25),
+                // unlabeled sensors are named after their file
+                ("k10temp", "temp3", 40.0),
+            ]
+        );
+        assert_eq!(sensors[0].max, Some(100.0));
+        assert_eq!(sensors[1].max, None);
+    }
+
+    #[test]
+    fn reads_fans_in_rpm() {
+        let root = fixture();
+        let fans = Hwmon::new(&*root).fans();
+        assert_eq!(
+            fans,
+            [Sensor {
+                chip: "amdgpu".to_owned(),
+                label: "fan1".to_owned(),
+                input: 1200.0,
+                max: Some(3300.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn matches_bare_and_chip_labels() {
+        let root = fixture();
+        let sensors = Hwmon::new(&*root).temperatures();
+        assert!(sensors[0].matches("edge"));
+        assert!(sensors[0].matches("amdgpu edge"));
+        assert!(!sensors[0].matches("k10temp edge"));
+        assert!(!sensors[0].matches("amdgpuedge"));
+    }
+
+    #[test]
+    fn missing_root_has_no_sensors() {
+        assert!(Hwmon::new("/nonexistent").temperatures().is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const INTERRUPTS_PATH: &str = "/proc/interrupts";
+
+/// Reader for the interrupts serviced by each IRQ line, in `/proc/interrupts`
+#[derive(Clone, Debug)]
+pub struct Interrupts {
+    path: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Irq {
+    /// number of the line (e.g. `16`), or name of an architecture-specific interrupt (e.g. `LOC`)
+    pub name: String,
+    /// controller and devices of the line, or description of the interrupt (e.g. `Local timer interrupts`)
+    pub description: String,
+    /// interrupts serviced by every cpu since boot
+    pub count: u64,
+}
+
+impl Default for Interrupts {
+    fn default() -> Self {
+        Self::new(INTERRUPTS_PATH)
+    }
+}
+
+impl Interrupts {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The IRQ lines, in the order reported by the kernel
+    pub fn read(&self) -> Vec<Irq> {
+        fs::read_to_string(&self.path)
+            .map(|content| parse(&content))
+            .unwrap_or_default()
+    }
+}
+
+fn parse(content: &str) -> Vec<Irq> {
+    let mut lines = content.lines();
+    // the header has a column per cpu
+    let Some(cpus) = lines.next().map(|header| header.split_whitespace().count()) else {
+        return Vec::new();
+    };
+
+    lines
+        .filter_map(|line| {
+            let (name, values) = line.split_once(':')?;
+            let mut fields = values.split_whitespace().peekable();
+            let mut count = 0;
+            // some lines (e.g. `ERR`, `MIS`) have a single column
+            for _ in 0..cpus {
+                let Some(value) = fields.peek().and_then(|field| field.parse::<u64>().ok()) else {
+                    break;
+                };
+                count += value;
+                fields.next();
+            }
+            Some(Irq {
+                name: name.trim().to_owned(),
+                description: fields.collect::<Vec<_>>().join(" "),
+                count,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const INTERRUPTS: &str = "           CPU0       CPU1       CPU2       CPU3       \n\
+                              \x20 0:         28          0          0          0  IR-IO-APIC    2-edge      timer\n\
+                              \x20 9:          0         12          3          0  IR-IO-APIC    9-fasteoi   acpi\n\
+                              128:       1500       2500          0       1000  IR-PCI-MSI-0000:00:14.3    0-edge      iwlwifi\n\
+                              NMI:          5          6          7          8   Non-maskable interrupts\n\
+                              LOC:     100000     200000     300000     400000   Local timer interrupts\n\
+                              ERR:          1\n\
+                              MIS:          0\n";
+
+    #[test]
+    fn sums_the_cpu_columns() {
+        let irqs = parse(INTERRUPTS);
+        let counts: Vec<_> = irqs
+            .iter()
+            .map(|irq| (irq.name.as_str(), irq.count))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("0", 28),
+                ("9", 15),
+                ("128", 5000),
+                ("NMI", 26),
+                ("LOC", 1_000_000),
+                ("ERR", 1),
+                ("MIS", 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_the_descriptions() {
+        let irqs = parse(INTERRUPTS);
+        assert_eq!(
+            irqs[2],
+            Irq {
+                name: "128".to_owned(),
+                description: "IR-PCI-MSI-0000:00:14.3 0-edge iwlwifi".to_owned(),
+                count: 5000,
+            }
+        );
+        assert_eq!(irqs[4].description, "Local timer interrupts");
+        // single column lines have none
+        assert_eq!(
+            irqs[5],
+            Irq {
+                name: "ERR".to_owned(),
+                description: String::new(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("interrupts", &[("interrupts", INTERRUPTS)]);
+        assert_eq!(Interrupts::new(root.join("interrupts")).read().len(), 7);
+        assert!(Interrupts::new(root.join("missing")).read().is_empty());
+        assert!(parse("").is_empty());
+    }
+}
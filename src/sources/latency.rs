@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{
+    io::Read,
+    net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often `ping` is checked for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatencySample {
+    /// address the probe was sent to, kept so that the target isn't resolved again
+    pub address: SocketAddr,
+    /// round-trip time, or `None` if the probe was lost
+    pub rtt: Option<Duration>,
+}
+
+/// Resolves `target`, a `host:port` pair or a bare host, to its first address, giving up after `timeout`
+///
+/// The lookup can't be cancelled, so it's left to finish on its own thread when it times out.
+pub fn resolve(target: &str, timeout: Duration) -> Option<SocketAddr> {
+    let target = target.to_owned();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // bare hosts, probed with ICMP, are resolved with a port that isn't used
+        let addresses = target
+            .to_socket_addrs()
+            .or_else(|_| (target.as_str(), 0).to_socket_addrs());
+        _ = sender.send(addresses.ok().and_then(|mut addresses| addresses.next()));
+    });
+    receiver.recv_timeout(timeout).ok().flatten()
+}
+
+/// Time taken to open a TCP connection to `address`
+pub fn tcp(address: SocketAddr, timeout: Duration) -> Option<Duration> {
+    let start = Instant::now();
+    TcpStream::connect_timeout(&address, timeout).ok()?;
+    Some(start.elapsed())
+}
+
+/// Round-trip time of a single ICMP echo request to `address`, sent by the `ping` command
+///
+/// Sending ICMP requires unprivileged ICMP sockets or a setuid `ping`, so it fails on some systems.
+pub fn icmp(address: IpAddr, timeout: Duration) -> Option<Duration> {
+    // `ping` only takes whole seconds on older versions, so it's killed once `timeout` is over
+    let wait = timeout.as_secs().max(1).to_string();
+    let mut child = Command::new("ping")
+        .args(["-n", "-c", "1", "-W", &wait, &address.to_string()])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+            // timed out, or the child can't be waited for
+            _ => {
+                _ = child.kill();
+                _ = child.wait();
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        return None;
+    }
+    // the output of a single request fits in the pipe, so it can be read after the exit
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    parse_ping(&output)
+}
+
+/// Reads the `time=<ms> ms` field of the reply line
+fn parse_ping(output: &str) -> Option<Duration> {
+    let (_, time) = output.split_once("time=")?;
+    let millis: f64 = time.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_secs_f64(millis / 1000.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, TcpListener};
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn resolves_addresses_and_bare_hosts() {
+        assert_eq!(
+            resolve("127.0.0.1:443", TIMEOUT),
+            Some(SocketAddr::from((Ipv4Addr::LOCALHOST, 443)))
+        );
+        assert_eq!(
+            resolve("::1", TIMEOUT).map(|address| address.ip()),
+            Some("::1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn times_tcp_connections() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        assert!(tcp(address, TIMEOUT).is_some());
+        drop(listener);
+        assert_eq!(tcp(address, TIMEOUT), None);
+    }
+
+    #[test]
+    fn parses_the_reply_time() {
+        let output = "PING 1.1.1.1 (1.1.1.1) 56(84) bytes of data.
+64 bytes from 1.1.1.1: icmp_seq=1 ttl=57 time=12.5 ms
+
+--- 1.1.1.1 ping statistics ---
+1 packets transmitted, 1 received, 0% packet loss, time 0ms
+";
+        assert_eq!(parse_ping(output), Some(Duration::from_micros(12_500)));
+        assert_eq!(parse_ping("1 packets transmitted, 0 received"), None);
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const MEMINFO_PATH: &str = "/proc/meminfo";
+
+/// Reader for the memory statistics exposed by the kernel in `/proc/meminfo`
+#[derive(Clone, Debug)]
+pub struct MemInfo {
+    path: PathBuf,
+}
+
+/// Memory statistics, in bytes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemInfoSample {
+    pub total: u64,
+    pub free: u64,
+    /// estimate of the memory available for new applications without swapping
+    pub available: u64,
+    pub buffers: u64,
+    /// page cache, including `shmem`, `dirty` and `writeback`
+    pub cached: u64,
+    /// shared memory and tmpfs, which can't be reclaimed without swapping
+    pub shmem: u64,
+    /// page cache waiting to be written to disk
+    pub dirty: u64,
+    /// page cache being written to disk
+    pub writeback: u64,
+}
+
+impl Default for MemInfo {
+    fn default() -> Self {
+        Self::new(MEMINFO_PATH)
+    }
+}
+
+impl MemInfo {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn read(&self) -> Option<MemInfoSample> {
+        parse(&fs::read_to_string(&self.path).ok()?)
+    }
+}
+
+fn parse(content: &str) -> Option<MemInfoSample> {
+    let (mut total, mut available) = (None, None);
+    let mut sample = MemInfoSample::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // values are reported in kB
+        let Some(value) = value
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(|value| value * 1024)
+        else {
+            continue;
+        };
+        match key {
+            "MemTotal" => total = Some(value),
+            "MemFree" => sample.free = value,
+            "MemAvailable" => available = Some(value),
+            "Buffers" => sample.buffers = value,
+            "Cached" => sample.cached = value,
+            "Shmem" => sample.shmem = value,
+            "Dirty" => sample.dirty = value,
+            "Writeback" => sample.writeback = value,
+            _ => {}
+        }
+    }
+
+    Some(MemInfoSample {
+        total: total?,
+        available: available?,
+        ..sample
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const MEMINFO: &str = "MemTotal:       16303524 kB\n\
+                           MemFree:         1045232 kB\n\
+                           MemAvailable:    9871600 kB\n\
+                           Buffers:          412788 kB\n\
+                           Cached:          8532104 kB\n\
+                           SwapCached:         1536 kB\n\
+                           Active:          7123456 kB\n\
+                           Shmem:            654320 kB\n\
+                           Dirty:               952 kB\n\
+                           Writeback:             0 kB\n\
+                           HugePages_Total:       0\n";
+
+    #[test]
+    fn parses_kilobytes() {
+        assert_eq!(
+            parse(MEMINFO),
+            Some(MemInfoSample {
+                total: 16_303_524 * 1024,
+                free: 1_045_232 * 1024,
+                available: 9_871_600 * 1024,
+                buffers: 412_788 * 1024,
+                cached: 8_532_104 * 1024,
+                shmem: 654_320 * 1024,
+                dirty: 952 * 1024,
+                writeback: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn total_and_available_are_required() {
+        assert_eq!(parse("MemTotal: 16303524 kB\nMemFree: 1045232 kB\n"), None);
+        assert_eq!(parse("MemAvailable: 9871600 kB\n"), None);
+        assert_eq!(
+            parse("MemTotal: 2048 kB\nMemAvailable: 1024 kB\n"),
+            Some(MemInfoSample {
+                total: 2048 * 1024,
+                available: 1024 * 1024,
+                ..MemInfoSample::default()
+            })
+        );
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("meminfo", &[("meminfo", MEMINFO)]);
+        assert_eq!(
+            MemInfo::new(root.join("meminfo")).read().unwrap().total,
+            16_303_524 * 1024
+        );
+        assert_eq!(MemInfo::new(root.join("missing")).read(), None);
+    }
+}
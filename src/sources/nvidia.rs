@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, error::NvmlError, Nvml};
+
+use super::gpu::{GpuBackend, GpuSample};
+
+/// GPU backend for the NVIDIA proprietary driver, through NVML
+pub struct Nvidia {
+    nvml: Nvml,
+}
+
+impl Nvidia {
+    /// Loads NVML, which fails on machines without the NVIDIA driver
+    pub fn init() -> Result<Self, NvmlError> {
+        Nvml::init().map(|nvml| Self { nvml })
+    }
+}
+
+impl GpuBackend for Nvidia {
+    /// Samples every device, named `nvidia<n>` after their NVML index
+    #[allow(clippy::cast_precision_loss)]
+    fn sample(&mut self) -> Vec<GpuSample> {
+        let count = self.nvml.device_count().unwrap_or_default();
+
+        (0..count)
+            .filter_map(|index| {
+                let device = self.nvml.device_by_index(index).ok()?;
+                let memory = device.memory_info().ok();
+                let milliwatts = |mw: u32| mw as f32 / 1000.0;
+
+                Some(GpuSample {
+                    card: format!("nvidia{index}"),
+                    usage: device
+                        .utilization_rates()
+                        .ok()
+                        .map(|utilization| utilization.gpu as f32),
+                    vram_used: memory.as_ref().map(|memory| memory.used),
+                    vram_total: memory.as_ref().map(|memory| memory.total),
+                    temperature: device
+                        .temperature(TemperatureSensor::Gpu)
+                        .ok()
+                        .map(|temp| temp as f32),
+                    power: device.power_usage().ok().map(milliwatts),
+                    power_limit: device.enforced_power_limit().ok().map(milliwatts),
+                })
+            })
+            .collect()
+    }
+}
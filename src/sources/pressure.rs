@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const PRESSURE_ROOT: &str = "/proc/pressure";
+
+/// Reader for the Pressure Stall Information exposed by the kernel under `/proc/pressure`
+#[derive(Clone, Debug)]
+pub struct Pressure {
+    root: PathBuf,
+}
+
+/// A line of a pressure file
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stall {
+    /// percentage of time stalled over the last 10 seconds
+    pub avg10: f32,
+    /// percentage of time stalled over the last 60 seconds
+    pub avg60: f32,
+    /// percentage of time stalled over the last 300 seconds
+    pub avg300: f32,
+    /// total time stalled, in microseconds
+    pub total: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PressureSample {
+    /// time in which at least one task was stalled
+    pub some: Stall,
+    /// time in which every non-idle task was stalled, not reported for the cpu by older kernels
+    pub full: Option<Stall>,
+}
+
+impl Default for Pressure {
+    fn default() -> Self {
+        Self::new(PRESSURE_ROOT)
+    }
+}
+
+impl Pressure {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Reads the pressure of `resource` (`cpu`, `memory` or `io`)
+    pub fn read(&self, resource: &str) -> Option<PressureSample> {
+        parse(&fs::read_to_string(self.root.join(resource)).ok()?)
+    }
+}
+
+fn parse(content: &str) -> Option<PressureSample> {
+    let (mut some, mut full) = (None, None);
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+
+        let mut stall = Stall::default();
+        for (key, value) in fields.filter_map(|field| field.split_once('=')) {
+            match key {
+                "avg10" => stall.avg10 = value.parse().ok()?,
+                "avg60" => stall.avg60 = value.parse().ok()?,
+                "avg300" => stall.avg300 = value.parse().ok()?,
+                "total" => stall.total = value.parse().ok()?,
+                _ => {}
+            }
+        }
+
+        match kind {
+            Some("some") => some = Some(stall),
+            Some("full") => full = Some(stall),
+            _ => {}
+        }
+    }
+
+    Some(PressureSample { some: some?, full })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const MEMORY: &str = "some avg10=1.50 avg60=0.75 avg300=0.25 total=123456\n\
+                          full avg10=0.50 avg60=0.00 avg300=0.10 total=6543\n";
+
+    #[test]
+    fn parses_some_and_full_lines() {
+        let sample = parse(MEMORY).unwrap();
+        assert_eq!(
+            sample.some,
+            Stall {
+                avg10: 1.5,
+                avg60: 0.75,
+                avg300: 0.25,
+                total: 123_456,
+            }
+        );
+        assert_eq!(
+            sample.full,
+            Some(Stall {
+                avg10: 0.5,
+                avg60: 0.0,
+                avg300: 0.1,
+                total: 6543,
+            })
+        );
+    }
+
+    #[test]
+    fn full_line_is_optional() {
+        let sample = parse("some avg10=2.00 avg60=1.00 avg300=0.50 total=42\n").unwrap();
+        assert_eq!(sample.some.total, 42);
+        assert_eq!(sample.full, None);
+    }
+
+    #[test]
+    fn some_line_is_required() {
+        assert_eq!(
+            parse("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n"),
+            None
+        );
+        assert_eq!(
+            parse("some avg10=nan? avg60=0.00 avg300=0.00 total=0\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_resources_under_the_root() {
+        let root = Fixture::new("pressure", &[("memory", MEMORY)]);
+        let pressure = Pressure::new(&*root);
+        assert_eq!(pressure.read("memory").unwrap().some.total, 123_456);
+        assert_eq!(pressure.read("io"), None);
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+use super::sysfs::read_value;
+
+pub const POWERCAP_ROOT: &str = "/sys/class/powercap";
+
+/// Reader for the RAPL energy counters exposed by the kernel under `/sys/class/powercap`
+///
+/// The counters are only readable by root on most distributions.
+#[derive(Clone, Debug)]
+pub struct Rapl {
+    root: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EnergyCounter {
+    /// name of the powercap zone (e.g. `intel-rapl:0`)
+    pub zone: String,
+    /// energy consumed, in microjoules
+    pub energy: u64,
+    /// value at which `energy` wraps around to 0
+    pub max_energy: u64,
+}
+
+impl EnergyCounter {
+    /// Energy consumed since `previous`, in microjoules, across a wraparound of the counter
+    pub fn since(&self, previous: &EnergyCounter) -> u64 {
+        if self.energy >= previous.energy {
+            self.energy - previous.energy
+        } else {
+            self.max_energy.saturating_sub(previous.energy) + self.energy
+        }
+    }
+}
+
+impl Default for Rapl {
+    fn default() -> Self {
+        Self::new(POWERCAP_ROOT)
+    }
+}
+
+impl Rapl {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Energy counters of the package zones (`intel-rapl:<n>`), sorted by zone
+    ///
+    /// Subzones (`intel-rapl:<n>:<m>`) are skipped, since they're included in their package, and so are the zones
+    /// whose counters can't be read, which is all of them for users other than root on most distributions.
+    pub fn packages(&self) -> Vec<EnergyCounter> {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return Vec::new();
+        };
+
+        let mut packages: Vec<_> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let zone = entry.file_name().into_string().ok()?;
+                let index = zone.strip_prefix("intel-rapl:")?;
+                if index.contains(':') {
+                    return None;
+                }
+                let path = entry.path();
+                Some(EnergyCounter {
+                    energy: read_value(&path.join("energy_uj"))?,
+                    max_energy: read_value(&path.join("max_energy_range_uj"))?,
+                    zone,
+                })
+            })
+            .collect();
+
+        packages.sort_by(|a, b| a.zone.cmp(&b.zone));
+        packages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    fn counter(energy: u64) -> EnergyCounter {
+        EnergyCounter {
+            zone: "intel-rapl:0".to_string(),
+            energy,
+            max_energy: 1000,
+        }
+    }
+
+    #[test]
+    fn energy_since_wraps_around_the_range() {
+        assert_eq!(counter(700).since(&counter(200)), 500);
+        assert_eq!(counter(100).since(&counter(900)), 200);
+    }
+
+    #[test]
+    fn reads_packages_but_not_subzones_or_unreadable_zones() {
+        let root = Fixture::new(
+            "rapl",
+            &[
+                ("intel-rapl:1/energy_uj", "2000\n"),
+                ("intel-rapl:1/max_energy_range_uj", "262143328850\n"),
+                ("intel-rapl:0/energy_uj", "1000\n"),
+                ("intel-rapl:0/max_energy_range_uj", "262143328850\n"),
+                ("intel-rapl:0:0/energy_uj", "500\n"),
+                ("intel-rapl:0:0/max_energy_range_uj", "262143328850\n"),
+                // the counter itself is missing, as if it was only readable by root
+                ("intel-rapl:2/max_energy_range_uj", "262143328850\n"),
+            ],
+        );
+        let packages = Rapl::new(&*root).packages();
+        let zones: Vec<_> = packages
+            .iter()
+            .map(|counter| (counter.zone.as_str(), counter.energy))
+            .collect();
+        assert_eq!(zones, [("intel-rapl:0", 1000), ("intel-rapl:1", 2000)]);
+    }
+}
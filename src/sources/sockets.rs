@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const PROC_NET_ROOT: &str = "/proc/net";
+
+/// Reader for the TCP connections and socket counts exposed by the kernel under `/proc/net`
+#[derive(Clone, Debug)]
+pub struct Sockets {
+    root: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SocketSample {
+    /// TCP connections, IPv4 and IPv6, in the `ESTABLISHED` state
+    pub established: u64,
+    /// TCP sockets, IPv4 and IPv6, in the `LISTEN` state
+    pub listen: u64,
+    /// TCP connections, IPv4 and IPv6, in the `TIME_WAIT` state
+    pub time_wait: u64,
+    /// sockets of every protocol in use
+    pub total: u64,
+}
+
+impl Default for Sockets {
+    fn default() -> Self {
+        Self::new(PROC_NET_ROOT)
+    }
+}
+
+impl Sockets {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn read(&self) -> Option<SocketSample> {
+        // `tcp6` is missing when IPv6 is disabled
+        let tables: Vec<_> = ["tcp", "tcp6"]
+            .into_iter()
+            .filter_map(|file| fs::read_to_string(self.root.join(file)).ok())
+            .collect();
+        let sockets_used = fs::read_to_string(self.root.join("sockstat"))
+            .ok()
+            .and_then(|content| parse_sockstat(&content));
+        if tables.is_empty() && sockets_used.is_none() {
+            return None;
+        }
+
+        let mut sample = SocketSample::default();
+        let mut tcp = 0;
+        for table in &tables {
+            // the first line is the header, and the 4th column is the state, in hexadecimal
+            for line in table.lines().skip(1) {
+                let Some(state) = line.split_whitespace().nth(3) else {
+                    continue;
+                };
+                match u8::from_str_radix(state, 16) {
+                    Ok(0x01) => sample.established += 1,
+                    Ok(0x06) => sample.time_wait += 1,
+                    Ok(0x0A) => sample.listen += 1,
+                    _ => {}
+                }
+                tcp += 1;
+            }
+        }
+        sample.total = sockets_used.unwrap_or(tcp);
+
+        Some(sample)
+    }
+}
+
+/// Reads the `sockets: used <n>` line of `sockstat`
+fn parse_sockstat(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("sockets: used "))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 21145 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:9E48 8E2BD9AC:01BB 01 00000000:00000000 02:000007D0 00000000  1000        0 68391 2 0000000000000000 24 4 30 10 -1
+   2: 0F02000A:D9C6 5DB8D822:01BB 06 00000000:00000000 03:00001522 00000000     0        0 0 3 0000000000000000
+";
+    const TCP6: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000000000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 20345 1 0000000000000000 100 0 0 10 0
+   1: 00000000000000000000000001000000:A1B2 00000000000000000000000001000000:0277 08 00000000:00000000 00:00000000 00000000  1000        0 70123 1 0000000000000000 20 4 0 10 -1
+";
+    const SOCKSTAT: &str = "sockets: used 731
+TCP: inuse 12 orphan 0 tw 1 alloc 15 mem 3
+UDP: inuse 8 mem 2
+";
+
+    #[test]
+    fn counts_tcp_states_of_both_tables() {
+        let root = Fixture::new(
+            "sockets",
+            &[("tcp", TCP), ("tcp6", TCP6), ("sockstat", SOCKSTAT)],
+        );
+        assert_eq!(
+            Sockets::new(&*root).read(),
+            Some(SocketSample {
+                established: 1,
+                listen: 2,
+                time_wait: 1,
+                total: 731,
+            })
+        );
+    }
+
+    #[test]
+    fn total_falls_back_to_the_tcp_sockets_without_sockstat() {
+        // IPv6 is disabled as well
+        let root = Fixture::new("sockets-tcp", &[("tcp", TCP)]);
+        let sample = Sockets::new(&*root).read().unwrap();
+        assert_eq!(sample.listen, 1);
+        assert_eq!(sample.total, 3);
+    }
+
+    #[test]
+    fn missing_tables_have_no_sample() {
+        let root = Fixture::new("sockets-empty", &[]);
+        assert_eq!(Sockets::new(&*root).read(), None);
+    }
+
+    #[test]
+    fn parses_the_used_sockets() {
+        assert_eq!(parse_sockstat(SOCKSTAT), Some(731));
+        assert_eq!(parse_sockstat("TCP: inuse 12\n"), None);
+    }
+}
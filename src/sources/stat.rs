@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const STAT_PATH: &str = "/proc/stat";
+
+/// Reader for the kernel statistics in `/proc/stat`
+#[derive(Clone, Debug)]
+pub struct ProcStat {
+    path: PathBuf,
+}
+
+/// Time spent by all the cpus in each state since boot, in clock ticks
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    /// time taken by the hypervisor for other virtual machines
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    pub fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// Time spent in each state since `previous`
+    #[must_use]
+    pub fn since(&self, previous: &CpuTimes) -> CpuTimes {
+        CpuTimes {
+            user: self.user.saturating_sub(previous.user),
+            nice: self.nice.saturating_sub(previous.nice),
+            system: self.system.saturating_sub(previous.system),
+            idle: self.idle.saturating_sub(previous.idle),
+            iowait: self.iowait.saturating_sub(previous.iowait),
+            irq: self.irq.saturating_sub(previous.irq),
+            softirq: self.softirq.saturating_sub(previous.softirq),
+            steal: self.steal.saturating_sub(previous.steal),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StatSample {
+    /// times of the `cpu` line, which sums every cpu
+    pub cpu: CpuTimes,
+    /// context switches since boot
+    pub context_switches: u64,
+    /// interrupts serviced since boot
+    pub interrupts: u64,
+    /// processes and threads created since boot
+    pub forks: u64,
+}
+
+impl Default for ProcStat {
+    fn default() -> Self {
+        Self::new(STAT_PATH)
+    }
+}
+
+impl ProcStat {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn read(&self) -> Option<StatSample> {
+        parse(&fs::read_to_string(&self.path).ok()?)
+    }
+}
+
+fn parse(content: &str) -> Option<StatSample> {
+    let mut cpu = None;
+    let mut sample = StatSample::default();
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some("cpu") => {
+                // older kernels don't report the last columns
+                let mut times = fields.map(|field| field.parse::<u64>().ok());
+                let mut next = || times.next().flatten().unwrap_or_default();
+                cpu = Some(CpuTimes {
+                    user: next(),
+                    nice: next(),
+                    system: next(),
+                    idle: next(),
+                    iowait: next(),
+                    irq: next(),
+                    softirq: next(),
+                    steal: next(),
+                });
+            }
+            Some("ctxt") => sample.context_switches = first_value(fields),
+            // followed by the count of each interrupt, after the total
+            Some("intr") => sample.interrupts = first_value(fields),
+            Some("processes") => sample.forks = first_value(fields),
+            _ => {}
+        }
+    }
+
+    Some(StatSample {
+        cpu: cpu?,
+        ..sample
+    })
+}
+
+fn first_value<'a>(mut fields: impl Iterator<Item = &'a str>) -> u64 {
+    fields
+        .next()
+        .and_then(|field| field.parse().ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const STAT: &str = "\
+cpu  4705 356 584 3699176 23060 0 277 0 0 0
+cpu0 1393280 32966 572056 13343292 6130 0 17875 0 23933 0
+intr 114930548 113199788 3 0 5 263 0 4 [...]
+ctxt 1990473
+btime 1062191376
+processes 2915
+procs_running 1
+procs_blocked 0
+";
+
+    #[test]
+    fn parses_the_cpu_line_and_counters() {
+        let sample = parse(STAT).unwrap();
+        assert_eq!(
+            sample,
+            StatSample {
+                cpu: CpuTimes {
+                    user: 4705,
+                    nice: 356,
+                    system: 584,
+                    idle: 3_699_176,
+                    iowait: 23060,
+                    irq: 0,
+                    softirq: 277,
+                    steal: 0,
+                },
+                context_switches: 1_990_473,
+                interrupts: 114_930_548,
+                forks: 2915,
+            }
+        );
+        assert_eq!(sample.cpu.total(), 3_728_158);
+    }
+
+    #[test]
+    fn missing_columns_are_0() {
+        let sample = parse("cpu  10 20 30 40\n").unwrap();
+        assert_eq!(sample.cpu.idle, 40);
+        assert_eq!(sample.cpu.iowait, 0);
+        assert_eq!(sample.cpu.steal, 0);
+        assert_eq!(sample.context_switches, 0);
+    }
+
+    #[test]
+    fn cpu_line_is_required() {
+        assert_eq!(parse("ctxt 1990473\n"), None);
+    }
+
+    #[test]
+    fn times_since_never_go_below_0() {
+        let previous = parse("cpu  10 0 10 100 5\n").unwrap().cpu;
+        let current = parse("cpu  15 0 8 130 5\n").unwrap().cpu;
+        let delta = current.since(&previous);
+        assert_eq!((delta.user, delta.system, delta.idle), (5, 0, 30));
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("stat", &[("stat", STAT)]);
+        assert_eq!(ProcStat::new(root.join("stat")).read().unwrap().forks, 2915);
+        assert_eq!(ProcStat::new(root.join("missing")).read(), None);
+    }
+}
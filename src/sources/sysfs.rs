@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::Path, str::FromStr};
+
+/// Reads a single-value pseudo-file, without the trailing newline
+pub fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_owned())
+}
+
+pub fn read_value<T: FromStr>(path: &Path) -> Option<T> {
+    read_trimmed(path)?.parse().ok()
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const PROC_ROOT: &str = "/proc";
+
+/// Reader for the processes listed under `/proc`
+#[derive(Clone, Debug)]
+pub struct Tasks {
+    root: PathBuf,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TaskSample {
+    pub processes: u64,
+    /// threads of every process, including their main thread
+    pub threads: u64,
+    /// processes that exited but weren't reaped by their parent
+    pub zombies: u64,
+}
+
+impl Default for Tasks {
+    fn default() -> Self {
+        Self::new(PROC_ROOT)
+    }
+}
+
+impl Tasks {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn read(&self) -> Option<TaskSample> {
+        let mut sample = TaskSample::default();
+        for entry in fs::read_dir(&self.root).ok()?.flatten() {
+            let is_pid = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.bytes().all(|byte| byte.is_ascii_digit()));
+            if !is_pid {
+                continue;
+            }
+            // processes can exit while they're listed
+            let Ok(stat) = fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+            // the name is between parentheses and can contain spaces, so the fields start after the last one
+            let Some((_, fields)) = stat.rsplit_once(')') else {
+                continue;
+            };
+            let mut fields = fields.split_whitespace();
+            let state = fields.next();
+            let threads = fields.nth(16).and_then(|field| field.parse::<u64>().ok());
+
+            sample.processes += 1;
+            sample.threads += threads.unwrap_or(1);
+            if state == Some("Z") {
+                sample.zombies += 1;
+            }
+        }
+
+        Some(sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    #[test]
+    fn counts_processes_threads_and_zombies() {
+        let root = Fixture::new(
+            "tasks",
+            &[
+                (
+                    "1/stat",
+                    "1 (systemd) S 0 1 1 0 -1 4194560 51874 1638466 118 1409 43 62 4329 2080 20 0 1 0 28 \
+                     24100864 3318 18446744073709551615 1 1 0 0 0 0 671173123 4096 1260 0 0 0 17 3 0 0",
+                ),
+                (
+                    "742/stat",
+                    "742 (Web Content (x)) R 1 742 742 0 -1 4194560 1 0 0 0 0 0 0 0 20 0 27 0 80 \
+                     24100864 3318 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 3 0 0",
+                ),
+                (
+                    "800/stat",
+                    "800 (defunct) Z 742 800 800 0 -1 4227140 0 0 0 0 0 0 0 0 20 0 1 0 90 0 0",
+                ),
+                // not a process
+                ("self/stat", "1 (systemd) S 0 1 1 0 -1 4194560 1 0 0 0 0 0 0 0 20 0 1 0 28"),
+                // exited while listed
+                ("900/status", "Name:\tgone\n"),
+            ],
+        );
+        assert_eq!(
+            Tasks::new(&*root).read(),
+            Some(TaskSample {
+                processes: 3,
+                threads: 29,
+                zombies: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn missing_root_has_no_sample() {
+        assert_eq!(Tasks::new("/nonexistent").read(), None);
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const VMSTAT_PATH: &str = "/proc/vmstat";
+
+/// Reader for the virtual memory counters in `/proc/vmstat`
+#[derive(Clone, Debug)]
+pub struct VmStat {
+    path: PathBuf,
+}
+
+/// Virtual memory counters since boot
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VmStatSample {
+    /// pages read from swap
+    pub swap_in: u64,
+    /// pages written to swap
+    pub swap_out: u64,
+    /// page faults that required reading from disk
+    pub major_faults: u64,
+    /// processes killed by the OOM killer, which is only reported since Linux 4.13
+    pub oom_kills: u64,
+}
+
+impl Default for VmStat {
+    fn default() -> Self {
+        Self::new(VMSTAT_PATH)
+    }
+}
+
+impl VmStat {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn read(&self) -> Option<VmStatSample> {
+        Some(parse(&fs::read_to_string(&self.path).ok()?))
+    }
+}
+
+fn parse(content: &str) -> VmStatSample {
+    let mut sample = VmStatSample::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse() else {
+            continue;
+        };
+        match key {
+            "pswpin" => sample.swap_in = value,
+            "pswpout" => sample.swap_out = value,
+            "pgmajfault" => sample.major_faults = value,
+            "oom_kill" => sample.oom_kills = value,
+            _ => {}
+        }
+    }
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const VMSTAT: &str = "nr_free_pages 261341\n\
+                          pgpgin 8421060\n\
+                          pswpin 1234\n\
+                          pswpout 5678\n\
+                          pgfault 98765432\n\
+                          pgmajfault 4321\n\
+                          oom_kill 2\n";
+
+    #[test]
+    fn parses_swap_faults_and_oom_kills() {
+        assert_eq!(
+            parse(VMSTAT),
+            VmStatSample {
+                swap_in: 1234,
+                swap_out: 5678,
+                major_faults: 4321,
+                oom_kills: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn oom_kills_are_optional() {
+        // before Linux 4.13
+        let sample = parse("pswpin 1\npswpout 2\npgmajfault 3\n");
+        assert_eq!(sample.major_faults, 3);
+        assert_eq!(sample.oom_kills, 0);
+        assert_eq!(parse("oom_kill ?\n"), VmStatSample::default());
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("vmstat", &[("vmstat", VMSTAT)]);
+        assert_eq!(
+            VmStat::new(root.join("vmstat")).read().unwrap().swap_out,
+            5678
+        );
+        assert_eq!(VmStat::new(root.join("missing")).read(), None);
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::{fs, path::PathBuf};
+
+pub const WIRELESS_PATH: &str = "/proc/net/wireless";
+
+/// Link quality reported by most drivers for a perfect link
+pub const MAX_QUALITY: f32 = 70.0;
+
+/// Reader for the signal of the wireless interfaces in `/proc/net/wireless`
+#[derive(Clone, Debug)]
+pub struct Wireless {
+    path: PathBuf,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WirelessSample {
+    /// name of the interface (e.g. `wlan0`)
+    pub interface: String,
+    /// link quality, usually out of [`MAX_QUALITY`]
+    pub quality: f32,
+    /// signal level, in dBm
+    pub level: f32,
+}
+
+impl Default for Wireless {
+    fn default() -> Self {
+        Self::new(WIRELESS_PATH)
+    }
+}
+
+impl Wireless {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The wireless interfaces, in the order reported by the kernel
+    pub fn read(&self) -> Vec<WirelessSample> {
+        fs::read_to_string(&self.path)
+            .map(|content| parse(&content))
+            .unwrap_or_default()
+    }
+}
+
+fn parse(content: &str) -> Vec<WirelessSample> {
+    // the first 2 lines are the header
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (interface, values) = line.split_once(':')?;
+            // values updated since the last read are followed by a dot
+            let mut values = values
+                .split_whitespace()
+                .skip(1)
+                .map(|value| value.trim_end_matches('.').parse::<f32>().ok());
+            let quality = values.next()??;
+            let level = values.next()??;
+            Some(WirelessSample {
+                interface: interface.trim().to_owned(),
+                quality,
+                // old drivers report the level as an unsigned byte
+                level: if level > 0.0 { level - 256.0 } else { level },
+            })
+        })
+        .collect()
+}
+
+/// Finds the interface named `name`, or the first interface if no name is given
+pub fn find<'a>(samples: &'a [WirelessSample], name: Option<&str>) -> Option<&'a WirelessSample> {
+    match name {
+        Some(name) => samples.iter().find(|sample| sample.interface == name),
+        None => samples.first(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::fixture::Fixture;
+
+    const WIRELESS: &str = "\
+Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+ face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+wlp3s0: 0000   54.  -56.  -256        0      0      0      0    133        0
+ wlan1: 0000   30     195.    0        0      0      0      0      0        0
+";
+
+    #[test]
+    fn skips_the_header_and_trailing_dots() {
+        let samples = parse(WIRELESS);
+        let values: Vec<_> = samples
+            .iter()
+            .map(|sample| (sample.interface.as_str(), sample.quality, sample.level))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("wlp3s0", 54.0, -56.0),
+                // reported as an unsigned byte
+                ("wlan1", 30.0, -61.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_the_interface_or_the_first_one() {
+        let samples = parse(WIRELESS);
+        assert_eq!(find(&samples, None).unwrap().interface, "wlp3s0");
+        assert_eq!(find(&samples, Some("wlan1")).unwrap().interface, "wlan1");
+        assert_eq!(find(&samples, Some("wlan2")), None);
+    }
+
+    #[test]
+    fn reads_the_file() {
+        let root = Fixture::new("wireless", &[("wireless", WIRELESS)]);
+        assert_eq!(Wireless::new(root.join("wireless")).read().len(), 2);
+        assert!(Wireless::new(root.join("missing")).read().is_empty());
+    }
+}